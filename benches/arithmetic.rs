@@ -0,0 +1,163 @@
+//! Timings of the core operations for a few primes.
+//!
+//! Run by ```cargo bench```. Each line shows the minimum and the median of the time per call over ```SAMPLES``` samples,
+//! measured after a warm-up.
+
+use ecdh_demo_in_rust::elliptic_curve::hasse_interval;
+use ecdh_demo_in_rust::inverse::Inverse;
+use ecdh_demo_in_rust::modint::ModInt;
+use ecdh_demo_in_rust::polynomial::Polynomial;
+use ecdh_demo_in_rust::rational_point::RationalPoint;
+use ecdh_demo_in_rust::util::naf;
+use ecdh_demo_in_rust::{complex, modint};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use std::time::Instant;
+
+/// The number of samples taken by ```bench```.
+const SAMPLES: u32 = 15;
+
+/// Run ```f``` for about ```iterations``` times and print the minimum and the median time per call.
+///
+/// The calls are split into ```SAMPLES``` samples of equal size, after a warm-up of one more sample which is not recorded.
+fn bench<F: FnMut()>(name: &str, iterations: u32, mut f: F) {
+    let per_sample = (iterations / SAMPLES).max(1);
+    for _ in 0..per_sample {
+        f();
+    }
+    let mut samples: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..per_sample {
+                f();
+            }
+            start.elapsed().as_nanos() as f64 / per_sample as f64
+        })
+        .collect();
+    samples.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    println!(
+        "{:50} {:>14.1} ns/iter (min) {:>14.1} ns/iter (median)",
+        name,
+        samples[0],
+        samples[samples.len() / 2]
+    );
+}
+
+/// Benchmarks over ```F_MOD``` and ```F_(MOD^2)```. ```MOD``` must be a prime with ```MOD % 4 == 3```.
+fn bench_prime<const MOD: u64>(rng: &mut StdRng) {
+    println!("p = {}", MOD);
+
+    bench("ModInt::new for 0..1000", 10_000, || {
+        for n in 0..black_box(1000) {
+            black_box(ModInt::<MOD>::new(n));
+        }
+    });
+    bench("ModInt::new_unchecked for 0..1000", 10_000, || {
+        for n in 0..black_box(1000) {
+            black_box(ModInt::<MOD>::new_unchecked(n));
+        }
+    });
+
+    let x = modint!(MOD; rng.gen_range(1, MOD));
+    bench("ModInt::power", 100_000, || {
+        black_box(black_box(x).power(black_box(MOD - 2)));
+    });
+    bench("ModInt::inverse", 100_000, || {
+        black_box(black_box(x).inverse());
+    });
+
+    let z = complex!(MOD; rng.gen_range(1, MOD), rng.gen_range(1, MOD));
+    bench("Complex::inverse", 10_000, || {
+        black_box(black_box(z).inverse());
+    });
+
+    let a = complex!(MOD; rng.gen_range(1, MOD), 0);
+    let b = complex!(MOD; rng.gen_range(1, MOD), 0);
+    let p = RationalPoint::random_point(a, b, rng);
+    let q = RationalPoint::random_point(a, b, rng);
+    bench("RationalPoint::add_rational_points", 10_000, || {
+        black_box(black_box(p).add_rational_points(&q, a));
+    });
+
+    // The order of E(F_(p^2)) is about p^2, so the baby-step giant-step takes O(p) steps
+    // and the repeated addition O(p^2) steps. They are timed only for small p.
+    let (_, upper) = hasse_interval(MOD * MOD);
+    if MOD < 1 << 17 {
+        bench("RationalPoint::order (baby-step giant-step)", 10, || {
+            black_box(black_box(p).order(a, upper));
+        });
+    }
+    if MOD < 1000 {
+        bench("RationalPoint::order_naive (repeated addition)", 10, || {
+            black_box(black_box(p).order_naive(a, upper));
+        });
+    }
+
+    let n = rng.gen_range(1, MOD * MOD);
+    bench(
+        "RationalPoint::multiply_rational_point (projective)",
+        1_000,
+        || {
+            black_box(black_box(p).multiply_rational_point(a, black_box(n)));
+        },
+    );
+    bench("RationalPoint::multiply_affine", 1_000, || {
+        black_box(black_box(p).multiply_affine(a, black_box(n)));
+    });
+    bench("RationalPoint::multiply_naf", 1_000, || {
+        black_box(black_box(p).multiply_naf(a, black_box(n)));
+    });
+
+    // The binary method adds for every bit 1 and the NAF method for every non-zero digit.
+    let scalars: Vec<u64> = (0..1000).map(|_| rng.gen_range(1, MOD * MOD)).collect();
+    let binary: u32 = scalars.iter().map(|n| n.count_ones()).sum();
+    let signed: usize = scalars
+        .iter()
+        .map(|&n| naf(n).iter().filter(|&&d| d != 0).count())
+        .sum();
+    println!(
+        "{:50} {:>14.1} binary, {:.1} NAF",
+        "additions per scalar",
+        binary as f64 / scalars.len() as f64,
+        signed as f64 / scalars.len() as f64
+    );
+
+    println!();
+}
+
+/// The schoolbook multiplication ```*``` against ```karatsuba_mul``` over ```F_863``` for growing degrees,
+/// which shows the crossover around ```KARATSUBA_THRESHOLD```.
+fn bench_polynomial(rng: &mut StdRng) {
+    println!("polynomials over F_863");
+
+    for &n in &[16, 32, 64, 128, 256, 512, 1024] {
+        let f = Polynomial::new(&(0..n).map(|_| rng.gen::<ModInt<863>>()).collect::<Vec<_>>());
+        let g = Polynomial::new(&(0..n).map(|_| rng.gen::<ModInt<863>>()).collect::<Vec<_>>());
+        let iterations = 1_000_000 / (n * n) + 10;
+        bench(
+            &format!("Polynomial * for degree {}", n - 1),
+            iterations,
+            || {
+                black_box(black_box(f.clone()) * black_box(g.clone()));
+            },
+        );
+        bench(
+            &format!("Polynomial::karatsuba_mul for degree {}", n - 1),
+            iterations,
+            || {
+                black_box(black_box(&f).karatsuba_mul(black_box(&g)));
+            },
+        );
+    }
+
+    println!();
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(0);
+    bench_prime::<863>(&mut rng);
+    bench_prime::<65519>(&mut rng);
+    bench_prime::<1000003>(&mut rng);
+    bench_polynomial(&mut rng);
+}
@@ -0,0 +1,68 @@
+//! A ```#![no_std]``` crate using the arithmetic of ```ecdh-demo-in-rust``` with ```default-features = false```.
+//!
+//! It checks that the field and curve arithmetic keeps working without ```std```.
+//! Run ```cargo build``` and ```cargo test``` in this directory.
+
+#![no_std]
+
+use ecdh_demo_in_rust::complexification::Complex;
+use ecdh_demo_in_rust::modint::ModInt;
+use ecdh_demo_in_rust::rational_point::RationalPoint;
+use rand::Rng;
+
+pub type F = ModInt<863>;
+pub type C = Complex<F>;
+
+/// The coefficients of the curve ```y^2 = x^3 + 2x + 3``` over ```F_(863^2)```.
+pub fn curve() -> (C, C) {
+    (
+        Complex::new(F::new(2), F::new(0)),
+        Complex::new(F::new(3), F::new(0)),
+    )
+}
+
+/// A key exchange on the curve: the shared points computed by Alice and by Bob from a random base point.
+pub fn exchange<R: Rng>(rng: &mut R) -> (RationalPoint<C>, RationalPoint<C>) {
+    let (a, b) = curve();
+    let g = RationalPoint::random_point(a, b, rng);
+    let alice: u64 = rng.gen_range(1, 863 * 863);
+    let bob: u64 = rng.gen_range(1, 863 * 863);
+    let alice_public = g.multiply_ct(a, alice);
+    let bob_public = g.multiply_ct(a, bob);
+    (
+        bob_public.multiply_ct(a, alice),
+        alice_public.multiply_ct(a, bob),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecdh_demo_in_rust::identities::Identity;
+    use ecdh_demo_in_rust::inverse::Inverse;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn modint_arithmetic() {
+        let x = F::new(5);
+        assert_eq!(x * x.inverse().unwrap(), F::identity());
+        assert_eq!(x.power(862), F::identity());
+        assert_eq!(-x + x, F::new(0));
+    }
+
+    #[test]
+    fn point_multiplication_and_exchange() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (a, b) = curve();
+        let p = RationalPoint::random_point(a, b, &mut rng);
+        assert!(p.is_on_curve(a, b));
+        assert_eq!(
+            p.multiply_rational_point(a, 3),
+            p.add_rational_points(&p, a).add_rational_points(&p, a)
+        );
+        assert_eq!(p.multiply_ct(a, 1000), p.multiply_rational_point(a, 1000));
+        let (alice, bob) = exchange(&mut rng);
+        assert_eq!(alice, bob);
+    }
+}
@@ -1,8 +1,32 @@
 use crate::characteristic::Characteristic;
+use crate::ct_eq::{ConditionallySelectable, ConstantTimeEq};
+use crate::field_size::FieldSize;
 use crate::identities::{Identity, Zero};
 use crate::inverse::Inverse;
-use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::prime_field::PrimeField;
+use crate::signed::Signed;
+use crate::util::factorize;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Construct an element of ```F_(P^2)``` over ```ModInt<P>```,
+/// i.e. ```complex!(P; re, im)``` is ```Complex::new(ModInt::<P>::new(re), ModInt::<P>::new(im))```.
+/// The modulus can be omitted as ```complex!(re, im)``` if it is inferred.
+/// ```text
+/// let z = complex!(863; 3, 862);
+/// ```
+#[macro_export]
+macro_rules! complex {
+    ($p:expr; $re:expr, $im:expr) => {
+        $crate::complexification::Complex::new($crate::modint!($p; $re), $crate::modint!($p; $im))
+    };
+    ($re:expr, $im:expr) => {
+        $crate::complexification::Complex::new($crate::modint!($re), $crate::modint!($im))
+    };
+}
 
 /// Elements in ```R\[x\]/(x^2 + 1)```
 /// where ```R``` is a ring consisting of the objects of type ```T```.
@@ -15,10 +39,19 @@ pub struct Complex<T> {
 impl<T> Complex<T> {
     /// Constructor.
     pub fn new(real: T, imaginary: T) -> Self {
-        Self {
-            real: real,
-            imaginary: imaginary,
-        }
+        Self { real, imaginary }
+    }
+}
+
+impl<T: Copy> Complex<T> {
+    /// The real part.
+    pub fn real(&self) -> T {
+        self.real
+    }
+
+    /// The imaginary part.
+    pub fn imaginary(&self) -> T {
+        self.imaginary
     }
 }
 
@@ -35,6 +68,50 @@ impl<T: fmt::Display + Zero + Eq> fmt::Display for Complex<T> {
     }
 }
 
+/// Implementation of ```PartialEq<T>```, comparing with the embedded element ```[a+0x]```.
+impl<T: Zero + Eq> PartialEq<T> for Complex<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.real == *other && self.imaginary == T::zero()
+    }
+}
+
+impl<T: fmt::Display + Zero + Identity + Eq + Copy + Neg<Output = T> + Signed> Complex<T> {
+    /// A string using the sign of the components (see ```Signed```) instead of ```Display```,
+    /// e.g. ```(3 - i)``` instead of ```(3 + 862i)``` over ```F_863```.
+    pub fn to_signed_string(&self) -> String {
+        let signed = |t: T| {
+            if t.is_negative() {
+                format!("-{}", -t)
+            } else {
+                format!("{}", t)
+            }
+        };
+        let negative = self.imaginary.is_negative();
+        let b = if negative {
+            -self.imaginary
+        } else {
+            self.imaginary
+        };
+        let term = if b == T::identity() {
+            "i".to_string()
+        } else {
+            format!("{}i", b)
+        };
+        let sign = if negative { "-" } else { "+" };
+        if self.imaginary == T::zero() {
+            signed(self.real)
+        } else if self.real == T::zero() {
+            if negative {
+                format!("-{}", term)
+            } else {
+                term
+            }
+        } else {
+            format!("({} {} {})", signed(self.real), sign, term)
+        }
+    }
+}
+
 /// Implementation of ```Add```.
 impl<T: Copy + Add<Output = T> + Eq> Add for Complex<T> {
     type Output = Self;
@@ -93,6 +170,18 @@ impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Eq> Mul for
     }
 }
 
+/// Implementation of ```Mul<T>```, i.e. the scalar multiplication by an element of ```R```.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Complex<T> {
+    type Output = Self;
+    /// Overloading the operator ```*```, i.e. ```(a + bi) s = as + bsi```.
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            real: self.real * rhs,
+            imaginary: self.imaginary * rhs,
+        }
+    }
+}
+
 /// Implementation of ```MulAssign```.
 impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Eq> MulAssign for Complex<T> {
     /// Overloading the operator ```*=```.
@@ -127,6 +216,30 @@ impl<T: Copy + Zero> Zero for Complex<T> {
     }
 }
 
+/// Implementation of ```From<(T, T)>```, i.e. ```(a, b) -> [a+bx]```.
+impl<T> From<(T, T)> for Complex<T> {
+    fn from((real, imaginary): (T, T)) -> Self {
+        Self { real, imaginary }
+    }
+}
+
+/// Implementation of ```From<T>```, i.e. the embedding ```a -> [a+0x]```.
+impl<T: Zero> From<T> for Complex<T> {
+    fn from(real: T) -> Self {
+        Self {
+            real,
+            imaginary: T::zero(),
+        }
+    }
+}
+
+/// Implementation of ```Default```, returning ```0```.
+impl<T: Copy + Zero> Default for Complex<T> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
 /// Implementation of ```Identity``` defined in ```identities.rs```.
 impl<T: Copy + Zero + Identity> Identity for Complex<T> {
     /// A function that returns an object corresponding to ```[1+0x]``` in ```R\[x\]/(x^2 + 1)```.
@@ -161,8 +274,10 @@ impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Eq + Zero +
             let tmp_a = a;
             let tmp_b = b;
             a = tmp_a * tmp_a - tmp_b * tmp_b;
-            b = tmp_a * tmp_b + tmp_b * tmp_a;
-            m = m / 2;
+            // (a + bi)^2 = (a^2 - b^2) + 2abi, where ab is computed only once.
+            let ab = tmp_a * tmp_b;
+            b = ab + ab;
+            m /= 2;
         }
         Self {
             real: res_r,
@@ -171,6 +286,102 @@ impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Eq + Zero +
     }
 }
 
+impl<
+        T: Characteristic
+            + Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Eq
+            + Zero
+            + Identity,
+    > Complex<T>
+{
+    /// Euler's criterion, i.e. check whether ```x^((q - 1) / 2) = 1``` in ```R\[x\]/(x^2 + 1)``` with ```q = p^2```.
+    /// This function works well under the same assumption as ```inverse```, and it returns ```false``` for ```0```.
+    pub fn is_quadratic_residue(&self) -> bool {
+        let p = T::characteristic();
+        self.modpow((p * p - 1) / 2) == Self::identity()
+    }
+}
+
+impl<
+        T: Characteristic
+            + Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Neg<Output = T>
+            + Eq
+            + Zero
+            + Identity,
+    > Complex<T>
+{
+    /// A square root. If ```y^2 = x``` has a solution, this function returns ```Some(y)```, otherwise ```None```.
+    /// This function works well if ```R``` is ```F_p``` where ```p``` is prime and ```p = 3 mod 4```.
+    ///
+    /// The algorithm is the one for ```F_(p^2)``` due to Adj and Rodríguez-Henríquez.
+    pub fn sqrt(&self) -> Option<Self> {
+        if *self == Self::zero() {
+            return Some(*self);
+        }
+        let p = T::characteristic();
+        let minus_one = -Self::identity();
+        let a1 = self.modpow((p - 3) / 4);
+        let alpha = a1 * a1 * *self;
+        if alpha.modpow(p) * alpha == minus_one {
+            return None;
+        }
+        let x0 = a1 * *self;
+        let x = if alpha == minus_one {
+            Self::new(T::zero(), T::identity()) * x0
+        } else {
+            (Self::identity() + alpha).modpow((p - 1) / 2) * x0
+        };
+        if x * x == *self {
+            Some(x)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: PrimeField + Zero + Eq> Complex<T> {
+    /// The parity of the representative of the first non-zero component (the real part first).
+    ///
+    /// Since ```p``` is odd, ```-x``` has the opposite parity to ```x``` unless ```x = 0```,
+    /// so this can be used to distinguish the two square roots.
+    pub fn is_odd(&self) -> bool {
+        if self.real != T::zero() {
+            self.real.to_int() % 2 == 1
+        } else {
+            self.imaginary.to_int() % 2 == 1
+        }
+    }
+}
+
+/// Implementation of ```ConstantTimeEq``` defined in ```ct_eq.rs```.
+/// Both parts are always compared.
+impl<T: ConstantTimeEq> ConstantTimeEq for Complex<T> {
+    fn ct_eq(&self, other: &Self) -> bool {
+        self.real.ct_eq(&other.real) & self.imaginary.ct_eq(&other.imaginary)
+    }
+}
+
+/// Implementation of ```ConditionallySelectable``` defined in ```ct_eq.rs```.
+impl<T: ConditionallySelectable> ConditionallySelectable for Complex<T> {
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        Self {
+            real: T::conditional_select(&a.real, &b.real, choice),
+            imaginary: T::conditional_select(&a.imaginary, &b.imaginary, choice),
+        }
+    }
+}
+
+/// Implementation of ```Signed``` defined in ```signed.rs```.
+/// No element is regarded as negative.
+impl<T> Signed for Complex<T> {}
+
 /// Implementation of ```Characteristic``` defined in ```characteristic.rs```.
 impl<T: Characteristic> Characteristic for Complex<T> {
     fn characteristic() -> u64 {
@@ -178,6 +389,15 @@ impl<T: Characteristic> Characteristic for Complex<T> {
     }
 }
 
+/// Implementation of ```FieldSize``` defined in ```field_size.rs```.
+/// This is meaningful when ```x^2 + 1``` is irreducible, e.g. over ```F_p``` with ```p % 4 == 3```.
+impl<T: FieldSize> FieldSize for Complex<T> {
+    /// The degree is doubled since ```x^2 + 1``` has degree 2.
+    fn degree() -> u32 {
+        2 * T::degree()
+    }
+}
+
 impl<
         T: Characteristic
             + Copy
@@ -199,3 +419,221 @@ impl<
         }
     }
 }
+
+/// Implementation of ```num_traits::Zero```, delegating to ```Zero``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl<T: Copy + Add<Output = T> + Zero + Eq> num_traits::Zero for Complex<T> {
+    fn zero() -> Self {
+        <Self as Zero>::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == <Self as Zero>::zero()
+    }
+}
+
+/// Implementation of ```num_traits::One```, delegating to ```Identity``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl<T: Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Zero + Identity + Eq>
+    num_traits::One for Complex<T>
+{
+    fn one() -> Self {
+        Self::identity()
+    }
+}
+
+impl<
+        T: PrimeField
+            + Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Eq
+            + Zero
+            + Identity,
+    > Complex<T>
+{
+    /// The multiplicative order in ```F_(p^2)```, i.e. the smallest ```k > 0``` with ```self^k = 1```,
+    /// or ```None``` for ```0```. This function works well if ```p``` is prime and ```p = 3 mod 4```.
+    ///
+    /// As ```ModInt::multiplicative_order```, the prime factors of ```p^2 - 1``` are removed from ```k = p^2 - 1```
+    /// as long as ```self^k = 1``` still holds.
+    pub fn multiplicative_order(&self) -> Option<u64> {
+        if *self == Self::zero() {
+            return None;
+        }
+        let p = T::characteristic();
+        let mut k = p * p - 1;
+        for (q, e) in factorize(p * p - 1) {
+            for _ in 0..e {
+                if self.modpow(k / q) == Self::identity() {
+                    k /= q;
+                } else {
+                    break;
+                }
+            }
+        }
+        Some(k)
+    }
+}
+
+/// All the ```p^2``` elements ```a + bi``` with ```a, b``` in ```F_p```, in the lexicographic order of ```(a, b)```.
+pub fn all<T: PrimeField>() -> impl Iterator<Item = Complex<T>> {
+    let p = T::characteristic();
+    (0..p).flat_map(move |a| (0..p).map(move |b| Complex::new(T::from_int(a), T::from_int(b))))
+}
+
+/// A generator of the multiplicative group ```F_(p^2)*``` of order ```p^2 - 1```,
+/// namely the first one in the order ```1, 2, ..., p - 1, i, 1 + i, ...```.
+/// ```p``` must be a prime with ```p = 3 mod 4```, otherwise this function may not terminate.
+///
+/// An element ```x``` generates the group iff ```x^((p^2 - 1) / q) != 1``` for every prime factor ```q``` of ```p^2 - 1```.
+pub fn find_generator_fp2<
+    T: PrimeField + Copy + Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Eq + Zero + Identity,
+>() -> Complex<T> {
+    let p = T::characteristic();
+    let order = p * p - 1;
+    let primes: Vec<u64> = factorize(order).into_iter().map(|(q, _)| q).collect();
+    let mut n = 1;
+    loop {
+        let x = Complex::new(T::from_int(n % p), T::from_int(n / p));
+        if primes
+            .iter()
+            .all(|&q| x.modpow(order / q) != Complex::identity())
+        {
+            return x;
+        }
+        n += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modint::ModInt;
+    use alloc::vec::Vec;
+
+    type F = ModInt<863>;
+    type C = Complex<F>;
+
+    fn c(re: u64, im: u64) -> C {
+        Complex::new(F::new(re), F::new(im))
+    }
+
+    #[test]
+    fn sqrt_exists_exactly_for_squares() {
+        type C11 = Complex<ModInt<11>>;
+        let elements: Vec<C11> = (0..11 * 11)
+            .map(|n| Complex::new(ModInt::new(n / 11), ModInt::new(n % 11)))
+            .collect();
+        let squares: Vec<C11> = elements.iter().map(|&x| x * x).collect();
+        for x in elements {
+            match x.sqrt() {
+                Some(y) => assert_eq!(y * y, x),
+                None => assert!(!squares.contains(&x)),
+            }
+            assert_eq!(
+                x.is_quadratic_residue(),
+                x != C11::zero() && squares.contains(&x)
+            );
+        }
+        let x = c(123, 456);
+        assert_eq!((x * x).sqrt().map(|y| y * y), Some(x * x));
+    }
+
+    #[test]
+    fn modpow_matches_repeated_multiplication() {
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let x = Complex::new(rng.gen::<F>(), rng.gen::<F>());
+            let n = rng.gen_range(0, 200);
+            let mut expected = C::identity();
+            for _ in 0..n {
+                expected *= x;
+            }
+            assert_eq!(x.modpow(n), expected);
+        }
+        let x = c(3, 5);
+        assert_eq!(x.modpow(863 * 863 - 1), C::identity());
+    }
+
+    #[test]
+    fn signed_string_shows_subtraction() {
+        assert_eq!(c(3, 862).to_signed_string(), "(3 - i)");
+        assert_eq!(c(3, 1).to_signed_string(), "(3 + i)");
+        assert_eq!(c(860, 5).to_signed_string(), "(-3 + 5i)");
+        assert_eq!(c(860, 858).to_signed_string(), "(-3 - 5i)");
+        assert_eq!(c(0, 862).to_signed_string(), "-i");
+        assert_eq!(c(0, 1).to_signed_string(), "i");
+        assert_eq!(c(862, 0).to_signed_string(), "-1");
+        assert_eq!(c(0, 0).to_signed_string(), "0");
+        assert_eq!(c(3, 862).to_string(), "(3 + 862i)");
+    }
+
+    #[test]
+    fn generator_of_fp2_has_full_order() {
+        type C11 = Complex<ModInt<11>>;
+        let g: C11 = find_generator_fp2();
+        let mut power = g;
+        for _ in 1..120 {
+            assert_ne!(power, C11::identity());
+            power *= g;
+        }
+        assert_eq!(power, C11::identity());
+        let g: C = find_generator_fp2();
+        assert_eq!(g.modpow(863 * 863 - 1), C::identity());
+        assert_ne!(g.modpow((863 * 863 - 1) / 2), C::identity());
+        assert_ne!(g.modpow((863 * 863 - 1) / 3), C::identity());
+    }
+
+    #[test]
+    fn all_yields_every_element_of_fp2_once() {
+        let mut elements: Vec<(u64, u64)> = all::<ModInt<11>>()
+            .map(|x| (x.real().to_int(), x.imaginary().to_int()))
+            .collect();
+        assert_eq!(elements.len(), 121);
+        elements.sort_unstable();
+        elements.dedup();
+        assert_eq!(elements.len(), 121);
+    }
+
+    #[test]
+    fn macro_matches_constructor() {
+        assert_eq!(complex!(863; 3, 862), c(3, 862));
+        assert_eq!(complex!(863; 863, 864), c(0, 1));
+        let z: C = complex!(1, 2);
+        assert_eq!(z, c(1, 2));
+    }
+
+    #[test]
+    fn default_is_zero() {
+        assert_eq!(C::default(), C::zero());
+    }
+
+    #[test]
+    fn comparison_with_embedded_elements() {
+        assert!(c(3, 0) == F::new(3));
+        assert!(c(3, 1) != F::new(3));
+        assert!(c(0, 0) == F::zero());
+        assert!(c(4, 0) != F::new(3));
+    }
+
+    #[test]
+    fn scalar_multiplication_multiplies_both_components() {
+        let s = F::new(7);
+        assert_eq!(c(3, 5) * s, c(21, 35));
+        assert_eq!(c(3, 5) * s, c(3, 5) * Complex::new(s, F::zero()));
+        assert_eq!(c(862, 1) * F::new(862), c(1, 862));
+        assert_eq!(c(3, 5) * F::zero(), C::zero());
+    }
+
+    #[test]
+    fn conversions_from_tuples_and_base_field() {
+        assert_eq!(C::from((F::new(1), F::new(2))), c(1, 2));
+        let z: C = (F::new(862), F::zero()).into();
+        assert_eq!(z, c(862, 0));
+        assert_eq!(C::from(F::new(5)), c(5, 0));
+    }
+}
@@ -0,0 +1,82 @@
+/// Equality checked in time independent of the values, e.g. for shared secrets and derived keys.
+///
+/// The derived ```PartialEq``` returns as soon as a difference is found,
+/// so the time of a comparison with a secret tells how much of it an attacker has guessed correctly.
+/// Implementations compare all the parts and combine the results with ```&``` instead of ```&&```.
+pub trait ConstantTimeEq {
+    fn ct_eq(&self, other: &Self) -> bool;
+}
+
+/// Selection of one of two values without a branch on the choice, e.g. in the Montgomery ladder with a secret scalar.
+///
+/// ```conditional_select(a, b, choice)``` is ```a``` if ```choice``` is ```false``` and ```b``` if it is ```true```.
+/// Implementations select every integer inside by masks (see ```ct_select_u64```) instead of ```if```.
+pub trait ConditionallySelectable: Sized {
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self;
+}
+
+/// Compare two integers without branching, i.e. the most significant bit of ```d | -d``` is set unless ```d = a ^ b``` is ```0```.
+pub fn ct_eq_u64(a: u64, b: u64) -> bool {
+    let d = a ^ b;
+    (d | d.wrapping_neg()) >> 63 == 0
+}
+
+/// Select ```a``` or ```b``` without branching, i.e. ```a ^ (mask & (a ^ b))```
+/// where ```mask``` is all zeros for ```false``` and all ones for ```true```.
+pub fn ct_select_u64(a: u64, b: u64, choice: bool) -> u64 {
+    let mask = (choice as u64).wrapping_neg();
+    a ^ (mask & (a ^ b))
+}
+
+/// Compare byte strings by OR-accumulating the differences of all the bytes.
+/// Only the lengths, which are regarded as public, are compared by a branch.
+pub fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut acc: u8 = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        acc |= x ^ y;
+    }
+    ct_eq_u64(acc as u64, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn integer_comparison_and_selection() {
+        let values = [0, 1, 2, 1 << 63, u64::MAX - 1, u64::MAX];
+        for &a in values.iter() {
+            for &b in values.iter() {
+                assert_eq!(ct_eq_u64(a, b), a == b);
+                assert_eq!(ct_select_u64(a, b, false), a);
+                assert_eq!(ct_select_u64(a, b, true), b);
+            }
+        }
+        assert!(ct_eq_bytes(b"key", b"key"));
+        assert!(!ct_eq_bytes(b"key", b"kez"));
+        assert!(!ct_eq_bytes(b"key", b"keys"));
+        assert!(ct_eq_bytes(b"", b""));
+    }
+
+    #[test]
+    fn field_elements_agree_with_derived_equality() {
+        type F = ModInt<11>;
+        for x in crate::modint::all::<11>() {
+            for y in crate::modint::all::<11>() {
+                assert_eq!(x.ct_eq(&y), x == y);
+            }
+        }
+        let elements: Vec<Complex<F>> = crate::complexification::all::<F>().collect();
+        for x in elements.iter() {
+            for y in elements.iter() {
+                assert_eq!(x.ct_eq(y), x == y);
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use crate::complexification::Complex;
+use crate::ct_eq::ConstantTimeEq;
+use crate::dyn_modint::DynModInt;
+use crate::ecdh::{derive_key, shared_secret, transcript, validate_public_key};
+use crate::elliptic_curve::{hasse_interval, CurveParams, EllipticCurve};
+use crate::field_size::FieldSize;
+use crate::identities::Zero;
+use crate::rational_point::RationalPoint;
+use crate::util::{crt, factorize};
+use rand::Rng;
+use std::io::{self, Write};
+
+/// Run the demonstration over ```F_(p^2)``` and write it to ```out```, where all the random choices are made by ```rng```.
+/// If ```invalid_curve``` is ```true```, the invalid-curve attack is demonstrated after the key exchange.
+///
+/// ```p``` must be a prime with ```p % 4 == 3``` and ```7 <= p < 2^32```, which is set as the modulus of ```DynModInt```.
+pub fn run<R: Rng, W: Write>(
+    p: u64,
+    invalid_curve: bool,
+    rng: &mut R,
+    out: &mut W,
+) -> io::Result<()> {
+    DynModInt::set_modulus(p);
+
+    writeln!(
+        out,
+        "\nDemonstration of ECDH (Elliptic curve Diffie–Hellman key exchange).\n"
+    )?;
+
+    let (a, b) = loop {
+        let a: DynModInt = rng.gen();
+        let b: DynModInt = rng.gen();
+        if DynModInt::new(4) * a.power(3) + DynModInt::new(27) * b.power(2) != 0 {
+            break (a, b);
+        }
+    };
+
+    writeln!(
+        out,
+        "We consider the elliptic curve\ny^2 = x^3 + {0}x + {1}\nover F_({2}^2) = F_{2}[x]/(x^2 + 1) = F_{2}(i).\n",
+        a, b, p
+    )?;
+
+    let curve = EllipticCurve::new(Complex::from(a), Complex::from(b));
+
+    let point = RationalPoint::random_point(curve.a(), curve.b(), rng);
+
+    writeln!(out, "We start up with the rational point G = {}.\n", point)?;
+
+    // By the Hasse bound, the order of E(F_q) is at most q + 1 + 2√q, where q = p^2.
+    let (_, upper) = hasse_interval(Complex::<DynModInt>::field_order());
+    let params = match CurveParams::new(curve, point, upper) {
+        Some(params) => params,
+        None => {
+            writeln!(
+                out,
+                "The order of G is greater than the Hasse bound {}.\n",
+                upper
+            )?;
+            return Ok(());
+        }
+    };
+    let ord = params.order();
+
+    writeln!(out, "The order of G is {}.\n", ord)?;
+
+    let exchange = transcript(&params, rng);
+    let alice = exchange.alice();
+    let bob = exchange.bob();
+
+    writeln!(
+        out,
+        "1a. Alice chooses d_a = {} randomly and computes Q_a = d_a G = {}.\n",
+        alice.secret(),
+        alice.public()
+    )?;
+
+    writeln!(
+        out,
+        "1b. Bob chooses d_b = {} randomly and computes Q_b = d_b G = {}.\n",
+        bob.secret(),
+        bob.public()
+    )?;
+
+    writeln!(
+        out,
+        "2. Alice sends Q_a to Bob while Bob sends Q_b to Alice.\n"
+    )?;
+
+    validate_public_key(alice.public(), params.a(), params.b(), params.order())
+        .expect("Q_a is not a valid public key");
+    validate_public_key(bob.public(), params.a(), params.b(), params.order())
+        .expect("Q_b is not a valid public key");
+
+    let point_ba = *exchange.shared_secret();
+
+    let point_ab = shared_secret(bob.secret(), alice.public(), params.curve());
+
+    assert!(point_ab.ct_eq(&point_ba));
+
+    writeln!(out, "3a. Alice computes d_a Q_b = {}.\n", point_ba)?;
+
+    writeln!(out, "3b. Bob computes d_b Q_a = {}.\n", point_ab)?;
+
+    writeln!(out, "They coincide and can be used as a shared key.\n")?;
+
+    let key = derive_key(&point_ab, 32).expect("the shared point is O");
+    let key: String = key.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    writeln!(
+        out,
+        "4. Both derive the 256-bit key {} from the x-coordinate of the shared point.\n",
+        key
+    )?;
+
+    if invalid_curve {
+        invalid_curve_attack(p, &params, bob.secret(), rng, out)?;
+    }
+    Ok(())
+}
+
+/// The largest prime order of the small subgroups used in ```invalid_curve_attack```.
+const SMALL_PRIME_BOUND: u64 = 1000;
+
+/// Demonstrate the invalid-curve attack on Bob, who multiplies a received point by his secret ```d_b``` without validating it.
+///
+/// Since the addition formulas do not use ```b```, Bob in fact computes on ```y^2 = x^3 + ax + b'``` for any ```b'```.
+/// The attacker sends points of small prime orders ```l``` on such curves,
+/// solves the discrete logarithm of each of the replies to learn ```d_b mod l```,
+/// and recovers ```d_b``` by the Chinese remainder theorem once the product of the ```l``` exceeds the order of ```G```.
+fn invalid_curve_attack<R: Rng, W: Write>(
+    p: u64,
+    params: &CurveParams<Complex<DynModInt>>,
+    bob_secret: u64,
+    rng: &mut R,
+    out: &mut W,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "5. Suppose that Bob skips the validation of the received public keys.\n"
+    )?;
+
+    // The orders of points on the invalid curves are computed in O(p) steps.
+    if p >= 1 << 16 {
+        writeln!(
+            out,
+            "The invalid-curve attack is demonstrated only for p < 2^16.\n"
+        )?;
+        return Ok(());
+    }
+
+    let a = params.a();
+    let (_, upper) = hasse_interval(Complex::<DynModInt>::field_order());
+    let mut congruences: Vec<(u64, u64)> = Vec::new();
+    let mut modulus = 1;
+    while modulus < params.order() {
+        let b = Complex::new(rng.gen(), rng.gen());
+        if b == params.b()
+            || a * a * a * DynModInt::new(4) + b * b * DynModInt::new(27) == Complex::zero()
+        {
+            continue;
+        }
+        let point = RationalPoint::random_point(a, b, rng);
+        let ord = match point.order(a, upper) {
+            Some(ord) => ord,
+            None => continue,
+        };
+        for (l, _) in factorize(ord) {
+            if l > SMALL_PRIME_BOUND || congruences.iter().any(|&(_, m)| m == l) {
+                continue;
+            }
+            let q = point.multiply_rational_point(a, ord / l);
+            let error = validate_public_key(&q, a, params.b(), params.order())
+                .expect_err("a point on an invalid curve passed the validation");
+            let reply = shared_secret(bob_secret, &q, params.curve());
+            let r = q
+                .discrete_log(&reply, a, l)
+                .expect("the reply is not a multiple of the sent point");
+            write!(out, "The attacker sends Q = {} of order {} ", q, l)?;
+            writeln!(
+                out,
+                "on y^2 = x^3 + {}x + {}, which is rejected if validated ({}).",
+                a.to_signed_string(),
+                b.to_signed_string(),
+                error
+            )?;
+            writeln!(
+                out,
+                "Bob replies d_b Q = {}, from which d_b = {} (mod {}).\n",
+                reply, r, l
+            )?;
+            congruences.push((r, l));
+            modulus *= l;
+        }
+    }
+
+    // d_b < ord(G) <= modulus, so d_b is determined by the congruences.
+    let (recovered, modulus) = crt(&congruences).expect("the moduli are distinct primes");
+    writeln!(
+        out,
+        "By the Chinese remainder theorem, the attacker recovers d_b = {} (mod {}).\n",
+        recovered, modulus
+    )?;
+    if recovered == bob_secret {
+        writeln!(
+            out,
+            "It coincides with Bob's real secret d_b = {}.\n",
+            bob_secret
+        )?;
+    } else {
+        writeln!(
+            out,
+            "It differs from Bob's real secret d_b = {}.\n",
+            bob_secret
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn output(seed: u64, invalid_curve: bool) -> String {
+        let mut out = Vec::new();
+        run(
+            863,
+            invalid_curve,
+            &mut StdRng::seed_from_u64(seed),
+            &mut out,
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn same_seed_gives_same_output() {
+        assert_eq!(output(1, false), output(1, false));
+        assert_ne!(output(1, false), output(2, false));
+    }
+
+    #[test]
+    fn writer_receives_the_transcript() {
+        let out = output(0, false);
+        assert!(out.contains("They coincide and can be used as a shared key."));
+        assert!(out.contains("4. Both derive the 256-bit key"));
+    }
+
+    #[test]
+    fn invalid_curve_attack_recovers_the_secret() {
+        for seed in 0..3 {
+            let out = output(seed, true);
+            assert!(out.contains("It coincides with Bob's real secret"));
+            assert!(!output(seed, false).contains("skips the validation"));
+        }
+    }
+}
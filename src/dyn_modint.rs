@@ -0,0 +1,438 @@
+use crate::characteristic::Characteristic;
+use crate::ct_eq::{ct_eq_u64, ct_select_u64, ConditionallySelectable, ConstantTimeEq};
+use crate::error::Error;
+use crate::field_size::FieldSize;
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::prime_field::PrimeField;
+use crate::signed::Signed;
+use crate::util::{is_prime, mod_inverse, reduce_exponent};
+use alloc::string::{String, ToString};
+use core::array::TryFromSliceError;
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicU64, Ordering};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The modulus shared by the objects of type ```DynModInt``` in the current thread, where ```0``` means unset.
+    static MODULUS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// The modulus shared by all the objects of type ```DynModInt```, where ```0``` means unset.
+#[cfg(not(feature = "std"))]
+static MODULUS: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "std")]
+fn load_modulus() -> u64 {
+    MODULUS.with(|m| m.get())
+}
+
+#[cfg(feature = "std")]
+fn store_modulus(p: u64) {
+    MODULUS.with(|m| m.set(p));
+}
+
+#[cfg(not(feature = "std"))]
+fn load_modulus() -> u64 {
+    MODULUS.load(Ordering::SeqCst)
+}
+
+#[cfg(not(feature = "std"))]
+fn store_modulus(p: u64) {
+    MODULUS.store(p, Ordering::SeqCst);
+}
+
+/// Elements of ```Z / (p)Z``` where the modulus ```p``` is chosen at runtime.
+///
+/// Unlike ```ModInt```, the modulus is not a part of the type.
+/// It must be set by ```DynModInt::set_modulus``` before use, and every operation panics until then.
+/// With the feature ```std``` the modulus is kept per thread, so that threads working with different primes,
+/// e.g. parallel tests, do not interfere with each other; without it, one modulus is shared by the whole program.
+/// The modulus must be less than ```2^63```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DynModInt {
+    representative: u64,
+}
+
+impl DynModInt {
+    /// Set the modulus of the current thread (see ```DynModInt```).
+    /// Objects constructed before changing the modulus must not be used afterwards. It panics if ```p``` is ```0```.
+    pub fn set_modulus(p: u64) {
+        assert!(p != 0, "the modulus of DynModInt must not be 0");
+        store_modulus(p);
+    }
+
+    /// Set the modulus after checking that it is a prime less than ```2^63```.
+    pub fn try_set_modulus(p: u64) -> Result<(), Error> {
+        if p >= 1 << 63 {
+            return Err(Error::ModulusTooLarge);
+        }
+        if !is_prime(p) {
+            return Err(Error::CompositeModulus);
+        }
+        Self::set_modulus(p);
+        Ok(())
+    }
+
+    /// The current modulus. It panics if the modulus is not set yet.
+    pub fn modulus() -> u64 {
+        let p = load_modulus();
+        assert!(
+            p != 0,
+            "the modulus of DynModInt is not set, call DynModInt::set_modulus first"
+        );
+        p
+    }
+
+    /// Constructor.
+    pub fn new(n: u64) -> Self {
+        DynModInt {
+            representative: n % Self::modulus(),
+        }
+    }
+
+    /// The same element with the representative reduced modulo ```p```.
+    /// Every object is kept reduced by the constructors and the operators, so this is the identity for them;
+    /// ```Eq``` and ```Hash``` compare the representatives and rely on it.
+    pub fn canonicalize(&self) -> Self {
+        Self::new(self.representative)
+    }
+
+    /// Make it ```u64``` type.
+    pub fn to_int(&self) -> u64 {
+        self.representative
+    }
+
+    /// The centered representative in ```(-p/2, p/2]```, e.g. ```-1``` instead of ```p - 1```.
+    pub fn to_signed(&self) -> i64 {
+        let m = Self::modulus();
+        if self.representative > m / 2 {
+            self.representative as i64 - m as i64
+        } else {
+            self.representative as i64
+        }
+    }
+
+    /// The centered representative as a string, see ```to_signed```.
+    pub fn to_signed_string(&self) -> String {
+        self.to_signed().to_string()
+    }
+
+    /// The representative in little-endian bytes.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        self.representative.to_le_bytes()
+    }
+
+    /// Make it from little-endian bytes, reducing the integer modulo ```p```.
+    pub fn from_bytes(b: [u8; 8]) -> Self {
+        Self::new(u64::from_le_bytes(b))
+    }
+
+    /// Culculate exponentiation by repeated squaring.
+    pub fn power(&self, n: u64) -> Self {
+        let mut res = Self::identity();
+        let mut a = *self;
+        let mut m = n;
+        loop {
+            if m == 0 {
+                break;
+            }
+            if m % 2 == 1 {
+                res *= a;
+            }
+            a *= a;
+            m /= 2;
+        }
+        res
+    }
+
+    /// The same as ```power```, but ```n``` is reduced by ```reduce_exponent``` first,
+    /// where ```order``` is a multiple of the multiplicative order of ```self```, e.g. ```p - 1```.
+    pub fn power_with_order(&self, n: u64, order: u64) -> Self {
+        self.power(reduce_exponent(n, order))
+    }
+}
+
+/// Implementation of ```Display```.
+impl fmt::Display for DynModInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.representative)
+    }
+}
+
+/// Implementation of ```Distribution``` for ```Standard```, i.e. ```rng.gen::<DynModInt>()```.
+/// The elements are sampled uniformly from the whole ```Z / (p)Z``` including ```0```.
+impl Distribution<DynModInt> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DynModInt {
+        DynModInt::new(rng.gen_range(0, DynModInt::modulus()))
+    }
+}
+
+/// Implementation of ```Hash```.
+/// It is checked in debug builds that the representative is reduced, so that equal elements have equal hashes.
+impl Hash for DynModInt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        debug_assert!(self.representative < Self::modulus());
+        self.representative.hash(state);
+    }
+}
+
+/// Implementation of ```PartialEq<u64>```, comparing with ```DynModInt::new(n)```.
+impl PartialEq<u64> for DynModInt {
+    fn eq(&self, other: &u64) -> bool {
+        *self == DynModInt::new(*other)
+    }
+}
+
+/// Implementation of ```TryFrom<&[u8]>```, i.e. ```from_bytes``` for slices.
+/// It fails unless the slice has exactly 8 bytes.
+impl TryFrom<&[u8]> for DynModInt {
+    type Error = TryFromSliceError;
+    fn try_from(b: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bytes(b.try_into()?))
+    }
+}
+
+/// Implementation of ```Add```.
+impl Add for DynModInt {
+    type Output = Self;
+    /// Overloading the operator ```+```, masked as ```ModInt``` does instead of reducing ```self + rhs```.
+    fn add(self, rhs: Self) -> Self::Output {
+        let (r, carry) = self.representative.overflowing_add(rhs.representative);
+        DynModInt {
+            representative: r.wrapping_sub(
+                Self::modulus() & 0u64.wrapping_sub((carry | (r >= Self::modulus())) as u64),
+            ),
+        }
+    }
+}
+
+/// Implementation of ```AddAssign```.
+impl AddAssign for DynModInt {
+    /// Overloading the operator ```+=```.
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Implementation of ```Sub```.
+impl Sub for DynModInt {
+    type Output = Self;
+    /// Overloading the operator ```-```, masked as ```ModInt``` does instead of reducing ```self + p - rhs```.
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.representative, rhs.representative);
+        let r = a.wrapping_sub(b);
+        DynModInt {
+            representative: r.wrapping_add(Self::modulus() & 0u64.wrapping_sub((a < b) as u64)),
+        }
+    }
+}
+
+/// Implementation of ```SubAssign```.
+impl SubAssign for DynModInt {
+    /// Overloading the operator ```-=```.
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Implementation of ```Mul```.
+impl Mul for DynModInt {
+    type Output = Self;
+    /// Overloading the operator ```*```.
+    /// The product is computed in ```u128```, so that it does not overflow for large moduli.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let product = self.representative as u128 * rhs.representative as u128;
+        DynModInt {
+            representative: (product % Self::modulus() as u128) as u64,
+        }
+    }
+}
+
+/// Implementation of ```MulAssign```.
+impl MulAssign for DynModInt {
+    /// Overloading the operator ```*=```.
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// Implementation of ```Neg```.
+impl Neg for DynModInt {
+    type Output = Self;
+    /// Overloading the operator ```-```, i.e. ```0 - self``` with the same masking as ```Sub```.
+    fn neg(self) -> Self {
+        let a = self.representative;
+        let r = 0u64.wrapping_sub(a);
+        DynModInt {
+            representative: r.wrapping_add(Self::modulus() & 0u64.wrapping_sub((a != 0) as u64)),
+        }
+    }
+}
+
+/// Implementation of ```Zero``` defined in ```identities.rs```.
+impl Zero for DynModInt {
+    /// A function that returns an object corresponding to ```0``` in ```Z / (p)Z```.
+    fn zero() -> Self {
+        DynModInt { representative: 0 }
+    }
+}
+
+/// Implementation of ```Default```, returning ```0```.
+impl Default for DynModInt {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// Implementation of ```Identity``` defined in ```identities.rs```.
+impl Identity for DynModInt {
+    /// A function that returns an object corresponding to ```1``` in ```Z / (p)Z```.
+    fn identity() -> Self {
+        DynModInt::new(1)
+    }
+}
+
+/// Implementation of ```ConstantTimeEq``` defined in ```ct_eq.rs```.
+impl ConstantTimeEq for DynModInt {
+    fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq_u64(self.representative, other.representative)
+    }
+}
+
+/// Implementation of ```ConditionallySelectable``` defined in ```ct_eq.rs```.
+impl ConditionallySelectable for DynModInt {
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        Self {
+            representative: ct_select_u64(a.representative, b.representative, choice),
+        }
+    }
+}
+
+/// Implementation of ```Signed``` defined in ```signed.rs```.
+impl Signed for DynModInt {
+    /// The elements whose centered representatives (see ```to_signed```) are negative.
+    fn is_negative(&self) -> bool {
+        self.to_signed() < 0
+    }
+}
+
+/// Implementation of ```Inverse``` defined in ```inverse.rs```.
+impl Inverse for DynModInt {
+    /// A function that returns an object corresponding to ```x^(-1)``` in ```Z / (p)Z```.
+    /// As for ```ModInt```, it is computed by the extended Euclidean algorithm, which is correct also for composite ```p```.
+    fn inverse(self) -> Option<DynModInt> {
+        mod_inverse(self.representative, Self::modulus()).map(|n| DynModInt { representative: n })
+    }
+}
+
+/// Implementation of ```Characteristic``` defined in ```characteristic.rs```.
+impl Characteristic for DynModInt {
+    /// A function that returns the characteristic of the fields dealing with.
+    fn characteristic() -> u64 {
+        Self::modulus()
+    }
+}
+
+/// Implementation of ```FieldSize``` defined in ```field_size.rs```.
+impl FieldSize for DynModInt {
+    /// ```Z / (p)Z``` is the prime field itself.
+    fn degree() -> u32 {
+        1
+    }
+}
+
+/// Implementation of ```PrimeField``` defined in ```prime_field.rs```.
+/// This works well when the modulus is prime.
+impl PrimeField for DynModInt {
+    fn from_int(n: u64) -> Self {
+        DynModInt::new(n)
+    }
+
+    fn to_int(&self) -> u64 {
+        self.representative
+    }
+}
+
+/// Implementation of ```num_traits::Zero```, delegating to ```Zero``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl num_traits::Zero for DynModInt {
+    fn zero() -> Self {
+        <Self as Zero>::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.representative == 0
+    }
+}
+
+/// Implementation of ```num_traits::One```, delegating to ```Identity``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl num_traits::One for DynModInt {
+    fn one() -> Self {
+        Self::identity()
+    }
+}
+
+// The tests rely on the modulus kept per thread, see ```DynModInt```.
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modulus_must_be_set_before_use() {
+        let result = std::thread::spawn(|| DynModInt::new(5)).join();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn modulus_is_kept_per_thread() {
+        DynModInt::set_modulus(863);
+        let other = std::thread::spawn(|| {
+            DynModInt::set_modulus(7);
+            DynModInt::new(10).to_int()
+        })
+        .join()
+        .unwrap();
+        assert_eq!(other, 3);
+        assert_eq!(DynModInt::modulus(), 863);
+        assert_eq!(DynModInt::new(870).to_int(), 7);
+    }
+
+    #[test]
+    fn try_set_modulus_rejects_bad_moduli() {
+        assert_eq!(DynModInt::try_set_modulus(15), Err(Error::CompositeModulus));
+        assert_eq!(
+            DynModInt::try_set_modulus((1 << 63) + 29),
+            Err(Error::ModulusTooLarge)
+        );
+        assert_eq!(DynModInt::try_set_modulus(863), Ok(()));
+        assert_eq!(DynModInt::modulus(), 863);
+    }
+
+    #[test]
+    fn subtraction_and_negation_near_the_largest_modulus() {
+        let p = 9223372036854775783;
+        DynModInt::set_modulus(p);
+        for &a in &[0, 1, p / 2, p - 1] {
+            for &b in &[0, 1, p / 2, p - 1] {
+                let (x, y) = (DynModInt::new(a), DynModInt::new(b));
+                let expected = ((a as u128 + p as u128 - b as u128) % p as u128) as u64;
+                assert_eq!((x - y).to_int(), expected);
+                assert_eq!(
+                    (x + y).to_int(),
+                    ((a as u128 + b as u128) % p as u128) as u64
+                );
+                assert_eq!((-y + y).to_int(), 0);
+            }
+        }
+    }
+}
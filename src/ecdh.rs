@@ -0,0 +1,336 @@
+#[cfg(feature = "kdf")]
+use crate::complexification::Complex;
+use crate::ct_eq::ConditionallySelectable;
+use crate::elliptic_curve::{CurveParams, EllipticCurve};
+use crate::field::Field;
+#[cfg(feature = "kdf")]
+use crate::prime_field::PrimeField;
+use crate::rational_point::RationalPoint;
+#[cfg(feature = "kdf")]
+use crate::sha256::sha256;
+#[cfg(feature = "kdf")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{AddAssign, MulAssign};
+use rand::Rng;
+
+/// A key pair of ECDH, i.e. a secret scalar ```d``` and the public point ```Q = dG```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct KeyPair<T> {
+    secret: u64,
+    public: RationalPoint<T>,
+}
+
+impl<T> KeyPair<T> {
+    /// The secret scalar ```d```.
+    pub fn secret(&self) -> u64 {
+        self.secret
+    }
+
+    /// The public point ```Q = dG```.
+    pub fn public(&self) -> &RationalPoint<T> {
+        &self.public
+    }
+}
+
+/// Generate a key pair, where ```g``` is the base point on ```curve``` and ```order``` is the order of ```g```.
+///
+/// The secret is chosen uniformly from ```[1, order)```, so ```order``` must be greater than 1.
+pub fn keygen<T: Field + ConditionallySelectable, R: Rng>(
+    curve: &EllipticCurve<T>,
+    g: &RationalPoint<T>,
+    order: u64,
+    rng: &mut R,
+) -> KeyPair<T> {
+    let secret = rng.gen_range(1, order);
+    KeyPair {
+        secret,
+        public: curve.mul_ct(g, secret),
+    }
+}
+
+/// The shared secret ```dQ``` computed from one's own secret ```d``` and the other party's public point ```Q```.
+///
+/// The public point should be checked by ```validate_public_key``` beforehand.
+pub fn shared_secret<T: Field + ConditionallySelectable>(
+    my_secret: u64,
+    their_public: &RationalPoint<T>,
+    curve: &EllipticCurve<T>,
+) -> RationalPoint<T> {
+    curve.mul_ct(their_public, my_secret)
+}
+
+/// The whole record of a key exchange between Alice and Bob,
+/// i.e. the parameters, both key pairs and the shared secret, e.g. for printing or checking a demonstration.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct EcdhTranscript<T> {
+    params: CurveParams<T>,
+    alice: KeyPair<T>,
+    bob: KeyPair<T>,
+    shared_secret: RationalPoint<T>,
+}
+
+impl<T> EcdhTranscript<T> {
+    /// The parameters, i.e. the curve, the generator ```G``` and its order.
+    pub fn params(&self) -> &CurveParams<T> {
+        &self.params
+    }
+
+    /// Alice's key pair ```(d_a, Q_a)```.
+    pub fn alice(&self) -> &KeyPair<T> {
+        &self.alice
+    }
+
+    /// Bob's key pair ```(d_b, Q_b)```.
+    pub fn bob(&self) -> &KeyPair<T> {
+        &self.bob
+    }
+
+    /// The shared secret ```d_a Q_b = d_b Q_a```.
+    pub fn shared_secret(&self) -> &RationalPoint<T> {
+        &self.shared_secret
+    }
+}
+
+/// Implementation of ```Display```, one item per line.
+impl<T: fmt::Display + Copy> fmt::Display for EcdhTranscript<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "curve: y^2 = x^3 + {}x + {}",
+            self.params.a(),
+            self.params.b()
+        )?;
+        writeln!(f, "generator: G = {}", self.params.generator())?;
+        writeln!(f, "order: {}", self.params.order())?;
+        writeln!(
+            f,
+            "Alice: d_a = {}, Q_a = {}",
+            self.alice.secret, self.alice.public
+        )?;
+        writeln!(
+            f,
+            "Bob: d_b = {}, Q_b = {}",
+            self.bob.secret, self.bob.public
+        )?;
+        write!(f, "shared secret: {}", self.shared_secret)
+    }
+}
+
+/// Run a key exchange between Alice and Bob with ```params```, where the secrets are chosen by ```rng```.
+///
+/// The shared secret is the one computed by Alice, which coincides with Bob's ```d_b Q_a```.
+pub fn transcript<T: Field + ConditionallySelectable, R: Rng>(
+    params: &CurveParams<T>,
+    rng: &mut R,
+) -> EcdhTranscript<T> {
+    let alice = keygen(params.curve(), &params.generator(), params.order(), rng);
+    let bob = keygen(params.curve(), &params.generator(), params.order(), rng);
+    EcdhTranscript {
+        params: *params,
+        alice,
+        bob,
+        shared_secret: shared_secret(alice.secret, &bob.public, params.curve()),
+    }
+}
+
+/// Reasons why a received public key is rejected.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum KeyError {
+    /// The key is the point at infinity ```O```.
+    PointAtInfinity,
+    /// The key does not lie on the curve.
+    NotOnCurve,
+    /// The key is not killed by the expected group order.
+    WrongOrder,
+}
+
+/// Implementation of ```Display```.
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyError::PointAtInfinity => write!(f, "the key is the point at infinity"),
+            KeyError::NotOnCurve => write!(f, "the key does not lie on the curve"),
+            KeyError::WrongOrder => write!(f, "the key does not have the expected order"),
+        }
+    }
+}
+
+/// Implementation of ```Error```.
+#[cfg(feature = "std")]
+impl std::error::Error for KeyError {}
+
+/// Validate a public key received from the other party before multiplying it by one's own secret.
+///
+/// It checks that the key is not ```O```, lies on the curve ```y^2 = x^3 + ax + b```
+/// and satisfies ```group_order * point = O```.
+/// Skipping these checks makes the exchange vulnerable to invalid-curve and small-subgroup attacks.
+pub fn validate_public_key<T: Field + AddAssign + MulAssign>(
+    point: &RationalPoint<T>,
+    a: T,
+    b: T,
+    group_order: u64,
+) -> Result<(), KeyError> {
+    if point.is_infinity() {
+        Err(KeyError::PointAtInfinity)
+    } else if !point.is_on_curve(a, b) {
+        Err(KeyError::NotOnCurve)
+    } else if point.multiply_rational_point(a, group_order) != RationalPoint::O {
+        Err(KeyError::WrongOrder)
+    } else {
+        Ok(())
+    }
+}
+
+/// Derive ```len``` bytes of key material from a shared point over ```F_(p^2)```.
+///
+/// The x-coordinate ```Z``` is serialized as the little-endian representatives of its real and imaginary parts,
+/// and the key is ```SHA-256(Z || 1) || SHA-256(Z || 2) || ...``` truncated to ```len``` bytes,
+/// where the counters are 32-bit big-endian integers (the KDF of ANSI X9.63).
+/// The point at infinity has no x-coordinate and is rejected.
+#[cfg(feature = "kdf")]
+pub fn derive_key<T: PrimeField + Copy>(
+    shared: &RationalPoint<Complex<T>>,
+    len: usize,
+) -> Result<Vec<u8>, KeyError> {
+    let (x, _) = shared.affine().ok_or(KeyError::PointAtInfinity)?;
+    let mut z: Vec<u8> = Vec::new();
+    z.extend_from_slice(&x.real().to_int().to_le_bytes());
+    z.extend_from_slice(&x.imaginary().to_int().to_le_bytes());
+    let mut key: Vec<u8> = Vec::new();
+    let mut counter: u32 = 1;
+    while key.len() < len {
+        let mut block = z.clone();
+        block.extend_from_slice(&counter.to_be_bytes());
+        key.extend_from_slice(&sha256(&block));
+        counter += 1;
+    }
+    key.truncate(len);
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::elliptic_curve::EllipticCurve;
+    use crate::modint::ModInt;
+    use alloc::format;
+    use alloc::string::ToString;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    type F = ModInt<863>;
+    type C = Complex<F>;
+
+    fn c(re: u64, im: u64) -> C {
+        Complex::new(F::new(re), F::new(im))
+    }
+
+    /// The parameters on ```y^2 = x^3 + 2x + 3``` over ```F_(863^2)``` with a generator given by ```find_generator```.
+    fn params(rng: &mut SmallRng) -> CurveParams<C> {
+        let curve = EllipticCurve::new(c(2, 0), c(3, 0));
+        let (g, order) =
+            RationalPoint::find_generator(curve.a(), curve.b(), curve.count_points(), rng).unwrap();
+        CurveParams::with_order(curve, g, order).unwrap()
+    }
+
+    #[test]
+    fn invalid_public_keys_are_rejected() {
+        let (a, b) = (c(2, 0), c(3, 0));
+        let mut rng = SmallRng::seed_from_u64(0);
+        let group_order = EllipticCurve::new(a, b).count_points();
+        let (g, order) = RationalPoint::find_generator(a, b, group_order, &mut rng).unwrap();
+        let public = g.multiply_rational_point(a, 12345 % order);
+        assert_eq!(validate_public_key(&public, a, b, order), Ok(()));
+        assert_eq!(
+            validate_public_key(&RationalPoint::O, a, b, order),
+            Err(KeyError::PointAtInfinity)
+        );
+        if let RationalPoint::Point(x, y) = public {
+            assert_eq!(
+                validate_public_key(&RationalPoint::Point(x, y + c(1, 0)), a, b, order),
+                Err(KeyError::NotOnCurve)
+            );
+        }
+        // A point outside the subgroup generated by G is on the curve but not killed by ord(G).
+        let outside = (0..)
+            .map(|_| RationalPoint::random_point(a, b, &mut rng))
+            .find(|p| p.multiply_rational_point(a, order) != RationalPoint::O)
+            .unwrap();
+        assert_eq!(
+            validate_public_key(&outside, a, b, order),
+            Err(KeyError::WrongOrder)
+        );
+    }
+
+    #[test]
+    fn alice_and_bob_share_the_secret() {
+        let curve = EllipticCurve::new(c(2, 0), c(3, 0));
+        let mut rng = SmallRng::seed_from_u64(1);
+        let (g, order) =
+            RationalPoint::find_generator(curve.a(), curve.b(), curve.count_points(), &mut rng)
+                .unwrap();
+        for _ in 0..20 {
+            let alice = keygen(&curve, &g, order, &mut rng);
+            let bob = keygen(&curve, &g, order, &mut rng);
+            assert!((1..order).contains(&alice.secret()));
+            assert_eq!(*alice.public(), curve.mul(&g, alice.secret()));
+            let shared_a = shared_secret(alice.secret(), bob.public(), &curve);
+            let shared_b = shared_secret(bob.secret(), alice.public(), &curve);
+            assert_eq!(shared_a, shared_b);
+            assert_eq!(shared_a, curve.mul(&g, alice.secret() * bob.secret()));
+        }
+    }
+
+    #[cfg(feature = "kdf")]
+    #[test]
+    fn derived_keys_agree_exactly_for_equal_points() {
+        let curve = EllipticCurve::new(c(2, 0), c(3, 0));
+        let mut rng = SmallRng::seed_from_u64(2);
+        let (g, order) =
+            RationalPoint::find_generator(curve.a(), curve.b(), curve.count_points(), &mut rng)
+                .unwrap();
+        let alice = keygen(&curve, &g, order, &mut rng);
+        let bob = keygen(&curve, &g, order, &mut rng);
+        let shared_a = shared_secret(alice.secret(), bob.public(), &curve);
+        let shared_b = shared_secret(bob.secret(), alice.public(), &curve);
+        let key_a = derive_key(&shared_a, 32).unwrap();
+        let key_b = derive_key(&shared_b, 32).unwrap();
+        assert_eq!(key_a, key_b);
+        assert_eq!(key_a.len(), 32);
+        // A longer key extends the shorter one, as the blocks are concatenated.
+        assert_eq!(derive_key(&shared_b, 80).unwrap()[..32], key_a[..]);
+        let other = curve.mul(&shared_a, 2);
+        assert_ne!(derive_key(&other, 32).unwrap(), key_a);
+        assert_eq!(
+            derive_key(&RationalPoint::<C>::O, 32),
+            Err(KeyError::PointAtInfinity)
+        );
+    }
+
+    #[test]
+    fn transcript_records_a_consistent_exchange() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let params = params(&mut rng);
+        for _ in 0..20 {
+            let t = transcript(&params, &mut rng);
+            let (alice, bob) = (t.alice(), t.bob());
+            let g = params.generator();
+            assert_eq!(t.params(), &params);
+            assert_eq!(*alice.public(), params.curve().mul(&g, alice.secret()));
+            assert_eq!(*bob.public(), params.curve().mul(&g, bob.secret()));
+            assert_eq!(
+                *t.shared_secret(),
+                shared_secret(alice.secret(), bob.public(), params.curve())
+            );
+            assert_eq!(
+                *t.shared_secret(),
+                shared_secret(bob.secret(), alice.public(), params.curve())
+            );
+            let text = t.to_string();
+            assert!(text.ends_with(&format!("shared secret: {}", t.shared_secret())));
+            assert_eq!(text.lines().count(), 6);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::characteristic::Characteristic;
+use crate::complexification::Complex;
+use crate::field::Field;
+use crate::identities::{Identity, Zero};
+use crate::montgomery::MontgomeryForm;
+use crate::rational_point::RationalPoint;
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+
+/// The twisted Edwards form ```ax^2 + y^2 = 1 + dx^2y^2``` birationally equivalent to a Montgomery form ```Bv^2 = u^3 + Au^2 + u```,
+/// where ```a = (A + 2) / B``` and ```d = (A - 2) / B```.
+///
+/// The maps are ```(u, v) -> (u / v, (u - 1) / (u + 1))``` and ```(x, y) -> ((1 + y) / (1 - y), (1 + y) / ((1 - y)x))```.
+/// Combined with ```MontgomeryForm```, it gives another model of the curve ```y^2 = x^3 + ax + b``` of the demo.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct TwistedEdwards<T> {
+    a: T,
+    d: T,
+    montgomery: MontgomeryForm<T>,
+}
+
+/// Points ```(x, y)``` on a twisted Edwards curve. Unlike ```RationalPoint```, the neutral element ```(0, 1)``` is an affine point.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct EdwardsPoint<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> EdwardsPoint<T> {
+    /// Constructor.
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy> EdwardsPoint<T> {
+    /// The coordinates ```(x, y)```.
+    pub fn coordinates(&self) -> (T, T) {
+        (self.x, self.y)
+    }
+}
+
+impl<T: Zero + Identity> EdwardsPoint<T> {
+    /// The neutral element ```(0, 1)```.
+    pub fn identity() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::identity(),
+        }
+    }
+}
+
+/// Implementation of ```Display```.
+impl<T: fmt::Display> fmt::Display for EdwardsPoint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Copy> TwistedEdwards<T> {
+    /// The coefficients ```(a, d)```.
+    pub fn coefficients(&self) -> (T, T) {
+        (self.a, self.d)
+    }
+
+    /// The Montgomery form which this curve is made from.
+    pub fn montgomery(&self) -> &MontgomeryForm<T> {
+        &self.montgomery
+    }
+}
+
+impl<T: Field> TwistedEdwards<T> {
+    /// The twisted Edwards form birationally equivalent to the Montgomery form ```m```.
+    pub fn from_montgomery(m: MontgomeryForm<T>) -> Self {
+        let id = T::identity();
+        let (a, b) = m.coefficients();
+        let b_inv = b.inverse().unwrap();
+        Self {
+            a: (a + id + id) * b_inv,
+            d: (a - id - id) * b_inv,
+            montgomery: m,
+        }
+    }
+
+    /// Check whether the point satisfies ```ax^2 + y^2 = 1 + dx^2y^2```.
+    pub fn is_on_curve(&self, p: &EdwardsPoint<T>) -> bool {
+        let xx = p.x * p.x;
+        let yy = p.y * p.y;
+        self.a * xx + yy == T::identity() + self.d * xx * yy
+    }
+
+    /// The addition law
+    /// ```(x1, y1) + (x2, y2) = ((x1y2 + y1x2) / (1 + dx1x2y1y2), (y1y2 - ax1x2) / (1 - dx1x2y1y2))```.
+    ///
+    /// The same formula is used for doublings and the neutral element.
+    /// It is complete, i.e. the denominators never vanish, if ```a``` is a square and ```d``` is not (see ```is_complete```),
+    /// and otherwise it returns ```None``` when one of them vanishes.
+    pub fn add(&self, p: &EdwardsPoint<T>, q: &EdwardsPoint<T>) -> Option<EdwardsPoint<T>> {
+        let id = T::identity();
+        let x1x2 = p.x * q.x;
+        let y1y2 = p.y * q.y;
+        let t = self.d * x1x2 * y1y2;
+        Some(EdwardsPoint {
+            x: (p.x * q.y + p.y * q.x) * (id + t).inverse()?,
+            y: (y1y2 - self.a * x1x2) * (id - t).inverse()?,
+        })
+    }
+
+    /// Calculate nP by repeated squaring, or ```None``` if an addition fails (see ```add```).
+    pub fn mul(&self, p: &EdwardsPoint<T>, n: u64) -> Option<EdwardsPoint<T>> {
+        let mut res = EdwardsPoint::identity();
+        let mut now = *p;
+        let mut m = n;
+        while m > 0 {
+            if m % 2 == 1 {
+                res = self.add(&res, &now)?;
+            }
+            now = self.add(&now, &now)?;
+            m /= 2;
+        }
+        Some(res)
+    }
+
+    /// Map a point on ```y^2 = x^3 + ax + b``` to this curve through the Montgomery form.
+    ///
+    /// ```O``` and the point ```(0, 0)``` of the Montgomery form go to ```(0, 1)``` and ```(0, -1)```.
+    /// It returns ```None``` for the other points with ```v = 0``` or ```u = -1```, where the map is not defined.
+    pub fn to_edwards_point(&self, p: &RationalPoint<T>) -> Option<EdwardsPoint<T>> {
+        let id = T::identity();
+        match self.montgomery.to_montgomery_point(p) {
+            RationalPoint::O => Some(EdwardsPoint::identity()),
+            RationalPoint::Point(u, v) => {
+                if u == T::zero() && v == T::zero() {
+                    return Some(EdwardsPoint {
+                        x: T::zero(),
+                        y: -id,
+                    });
+                }
+                Some(EdwardsPoint {
+                    x: u * v.inverse()?,
+                    y: (u - id) * (u + id).inverse()?,
+                })
+            }
+        }
+    }
+
+    /// The inverse of ```to_edwards_point```, or ```None``` for a point with ```y = 1``` other than the neutral element,
+    /// which does not lie on the curve.
+    pub fn from_edwards_point(&self, p: &EdwardsPoint<T>) -> Option<RationalPoint<T>> {
+        let id = T::identity();
+        if p.x == T::zero() {
+            return if p.y == id {
+                Some(RationalPoint::O)
+            } else {
+                let zero = RationalPoint::Point(T::zero(), T::zero());
+                Some(self.montgomery.from_montgomery_point(&zero))
+            };
+        }
+        let u = (id + p.y) * (id - p.y).inverse()?;
+        let v = u * p.x.inverse()?;
+        Some(
+            self.montgomery
+                .from_montgomery_point(&RationalPoint::Point(u, v)),
+        )
+    }
+}
+
+impl<
+        T: Characteristic
+            + Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Eq
+            + Zero
+            + Identity,
+    > TwistedEdwards<Complex<T>>
+{
+    /// Check whether the addition law is complete over ```F_(p^2)```, i.e. ```a``` is a square and ```d``` is not.
+    pub fn is_complete(&self) -> bool {
+        self.a.is_quadratic_residue() && !self.d.is_quadratic_residue()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_curves::{random_points, supersingular_curve};
+
+    #[test]
+    fn corresponding_points_give_corresponding_multiples() {
+        let (a, b) = supersingular_curve();
+        let edwards =
+            TwistedEdwards::from_montgomery(MontgomeryForm::from_weierstrass(a, b).unwrap());
+        let mut compared = 0;
+        for p in random_points(0).take(50) {
+            let e = match edwards.to_edwards_point(&p) {
+                Some(e) => e,
+                None => continue,
+            };
+            assert!(edwards.is_on_curve(&e));
+            assert_eq!(edwards.from_edwards_point(&e), Some(p));
+            for k in 0..44 {
+                let expected = edwards.to_edwards_point(&p.multiply_rational_point(a, k));
+                if let (Some(ke), Some(expected)) = (edwards.mul(&e, k), expected) {
+                    compared += 1;
+                    assert_eq!(ke, expected);
+                }
+            }
+            assert_eq!(edwards.mul(&e, 44), Some(EdwardsPoint::identity()));
+        }
+        assert!(compared > 1000);
+    }
+}
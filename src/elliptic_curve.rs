@@ -0,0 +1,557 @@
+use crate::complexification::{self, Complex};
+use crate::ct_eq::ConditionallySelectable;
+use crate::field::Field;
+use crate::identities::{Identity, Zero};
+use crate::prime_field::PrimeField;
+use crate::rational_point::RationalPoint;
+use crate::util::{gcd, isqrt};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// The elliptic curve ```y^2 = x^3 + ax + b```
+/// whose coefficients ```a``` and ```b``` are objects of type ```T```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct EllipticCurve<T> {
+    a: T,
+    b: T,
+}
+
+impl<T: Copy> EllipticCurve<T> {
+    /// Constructor.
+    pub fn new(a: T, b: T) -> Self {
+        Self { a, b }
+    }
+
+    /// The coefficient ```a```.
+    pub fn a(&self) -> T {
+        self.a
+    }
+
+    /// The coefficient ```b```.
+    pub fn b(&self) -> T {
+        self.b
+    }
+
+    /// Regard a rational point as a point on this curve, so that ```n * point``` can be written.
+    pub fn point(&self, p: RationalPoint<T>) -> CurvePoint<'_, T> {
+        CurvePoint {
+            curve: self,
+            point: p,
+        }
+    }
+}
+
+impl<T: Zero + Identity + Mul<Output = T> + MulAssign + AddAssign + Copy + Eq> EllipticCurve<T> {
+    /// Check whether a rational point lies on the curve.
+    pub fn is_on_curve(&self, p: &RationalPoint<T>) -> bool {
+        p.is_on_curve(self.a, self.b)
+    }
+}
+
+impl<T: Field + AddAssign + MulAssign> EllipticCurve<T> {
+    /// An addition of rational points on the curve.
+    ///
+    /// In debug builds, it panics unless both of the points lie on the curve,
+    /// since the formula for ```y^2 = x^3 + ax + b``` does not use ```b``` and gives garbage for points on other curves.
+    pub fn add(&self, p: &RationalPoint<T>, q: &RationalPoint<T>) -> RationalPoint<T> {
+        debug_assert!(self.is_on_curve(p), "the left operand is not on the curve");
+        debug_assert!(self.is_on_curve(q), "the right operand is not on the curve");
+        p.add_rational_points(q, self.a)
+    }
+}
+
+impl<T: Field> EllipticCurve<T> {
+    /// Calculate nP for a rational point P on the curve.
+    pub fn mul(&self, p: &RationalPoint<T>, n: u64) -> RationalPoint<T> {
+        p.multiply_rational_point(self.a, n)
+    }
+}
+
+impl<T: Field + ConditionallySelectable> EllipticCurve<T> {
+    /// Calculate nP for a rational point P on the curve in constant time, see ```RationalPoint::multiply_ct```.
+    pub fn mul_ct(&self, p: &RationalPoint<T>, n: u64) -> RationalPoint<T> {
+        p.multiply_ct(self.a, n)
+    }
+}
+
+impl<
+        T: PrimeField
+            + Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Eq
+            + Zero
+            + Identity,
+    > EllipticCurve<Complex<T>>
+{
+    /// The number of rational points on the curve over ```F_(p^2) = F_p[x]/(x^2 + 1)``` including ```O```,
+    /// i.e. the order of the group ```E(F_(p^2))```.
+    ///
+    /// For every x-coordinate, ```x^3 + ax + b``` has 2, 1 or 0 square roots according to
+    /// whether it is a non-zero quadratic residue, zero or a non-residue.
+    /// Since all the ```p^2``` x-coordinates are examined, this takes ```O(p^2)``` steps.
+    pub fn count_points(&self) -> u64 {
+        let mut count = 1;
+        for x in complexification::all::<T>() {
+            let r = x * x * x + self.a * x + self.b;
+            if r == Complex::zero() {
+                count += 1;
+            } else if r.is_quadratic_residue() {
+                count += 2;
+            }
+        }
+        count
+    }
+}
+
+impl<T: PrimeField + Field> EllipticCurve<Complex<T>> {
+    /// All the ```m```-torsion points of ```E(F_(p^2))```, i.e. the points ```P``` with ```mP = O```, including ```O```.
+    ///
+    /// Every point on the curve is examined, so this takes ```O(p^2 log m)``` steps.
+    /// If ```E[m]``` is contained in ```E(F_(p^2))```, there are ```m^2``` such points since ```E[m] = (Z/mZ)^2```.
+    pub fn torsion_points(&self, m: u64) -> Vec<RationalPoint<Complex<T>>> {
+        self.points()
+            .into_iter()
+            .filter(|p| self.mul(p, m) == RationalPoint::O)
+            .collect()
+    }
+
+    /// All the rational points of ```E(F_(p^2))``` including ```O```, which takes ```O(p^2)``` steps.
+    fn points(&self) -> Vec<RationalPoint<Complex<T>>> {
+        let mut points = vec![RationalPoint::O];
+        for x in complexification::all::<T>() {
+            if let Some(y) = (x * x * x + self.a * x + self.b).sqrt() {
+                points.push(RationalPoint::Point(x, y));
+                if y != Complex::zero() {
+                    points.push(RationalPoint::Point(x, -y));
+                }
+            }
+        }
+        points
+    }
+}
+
+impl<T: PrimeField + Field + fmt::Display> EllipticCurve<Complex<T>> {
+    /// Check the group laws of ```E(F_(p^2))``` by ```add_rational_points```, which is meant for small ```p```.
+    ///
+    /// For every point ```P```, it checks that ```P``` lies on the curve, ```P + O = O + P = P```, ```P + (-P) = O```,
+    /// and that ```2P``` and ```P + Q``` lie on the curve for a random point ```Q```.
+    /// The associativity ```(P + Q) + R = P + (Q + R)``` is checked for ```ASSOCIATIVITY_SAMPLES``` random triples,
+    /// where ```P + Q``` and ```Q + R``` are also checked to lie on the curve.
+    /// The random points are drawn from a fixed seed, so that the result is deterministic.
+    /// It returns a message describing the first failure.
+    pub fn verify_group_laws(&self) -> Result<(), String> {
+        const ASSOCIATIVITY_SAMPLES: usize = 1000;
+        let a = self.a;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let points = self.points();
+        let n = points.len();
+        let sum = |p: &RationalPoint<Complex<T>>, q: &RationalPoint<Complex<T>>| {
+            let sum = p.add_rational_points(q, a);
+            if sum.is_on_curve(self.a, self.b) {
+                Ok(sum)
+            } else {
+                Err(format!("{} + {} = {} does not lie on the curve", p, q, sum))
+            }
+        };
+        for p in points.iter() {
+            if !p.is_on_curve(self.a, self.b) {
+                return Err(format!("{} does not lie on the curve", p));
+            }
+            if p.add_rational_points(&RationalPoint::O, a) != *p
+                || RationalPoint::O.add_rational_points(p, a) != *p
+            {
+                return Err(format!("O is not the identity for {}", p));
+            }
+            if p.add_rational_points(&-*p, a) != RationalPoint::O {
+                return Err(format!("{} + (-{}) is not O", p, p));
+            }
+            sum(p, p)?;
+            sum(p, &points[rng.gen_range(0, n)])?;
+        }
+        for _ in 0..ASSOCIATIVITY_SAMPLES {
+            let (p, q, r) = (
+                &points[rng.gen_range(0, n)],
+                &points[rng.gen_range(0, n)],
+                &points[rng.gen_range(0, n)],
+            );
+            let left = sum(p, q)?.add_rational_points(r, a);
+            let right = p.add_rational_points(&sum(q, r)?, a);
+            if left != right {
+                return Err(format!(
+                    "({} + {}) + {} = {} differs from {} + ({} + {}) = {}",
+                    p, q, r, left, p, q, r, right
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The parameters of ECDH, i.e. an elliptic curve, a generator ```G``` on it and the order of ```G```.
+///
+/// The order is computed only once when it is made, and reused by the key generation and the key validation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CurveParams<T> {
+    curve: EllipticCurve<T>,
+    generator: RationalPoint<T>,
+    order: u64,
+}
+
+impl<T: Copy> CurveParams<T> {
+    /// The curve.
+    pub fn curve(&self) -> &EllipticCurve<T> {
+        &self.curve
+    }
+
+    /// The coefficient ```a``` of the curve.
+    pub fn a(&self) -> T {
+        self.curve.a
+    }
+
+    /// The coefficient ```b``` of the curve.
+    pub fn b(&self) -> T {
+        self.curve.b
+    }
+
+    /// The generator ```G```.
+    pub fn generator(&self) -> RationalPoint<T> {
+        self.generator
+    }
+
+    /// The order of ```G```.
+    pub fn order(&self) -> u64 {
+        self.order
+    }
+}
+
+impl<T: Field + AddAssign + MulAssign> CurveParams<T> {
+    /// Constructor with the order of ```G``` known in advance.
+    ///
+    /// It returns ```None``` unless ```G``` lies on the curve and ```order * G = O```.
+    /// Note that it is not checked that ```order``` is the smallest such one.
+    pub fn with_order(
+        curve: EllipticCurve<T>,
+        generator: RationalPoint<T>,
+        order: u64,
+    ) -> Option<Self> {
+        if order == 0
+            || !curve.is_on_curve(&generator)
+            || curve.mul(&generator, order) != RationalPoint::O
+        {
+            return None;
+        }
+        Some(Self {
+            curve,
+            generator,
+            order,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Field + AddAssign + MulAssign + Hash> CurveParams<T> {
+    /// Constructor computing the order of ```G``` by ```RationalPoint::order```.
+    ///
+    /// It returns ```None``` if ```G``` does not lie on the curve or its order is greater than ```upper```,
+    /// e.g. the upper end of ```hasse_interval```.
+    pub fn new(curve: EllipticCurve<T>, generator: RationalPoint<T>, upper: u64) -> Option<Self> {
+        if !curve.is_on_curve(&generator) {
+            return None;
+        }
+        let order = generator.order(curve.a, upper)?;
+        Some(Self {
+            curve,
+            generator,
+            order,
+        })
+    }
+}
+
+/// The Hasse interval ```[q + 1 - 2√q, q + 1 + 2√q]```, in which the order of ```E(F_q)``` lies
+/// for every elliptic curve ```E``` over ```F_q```.
+///
+/// ```q``` must be a field size, in particular ```0 < q < 2^63```, so that ```q + 1 + 2√q``` fits in ```u64```.
+pub fn hasse_interval(q: u64) -> (u64, u64) {
+    assert!(q != 0 && q < 1 << 63, "q must be in (0, 2^63)");
+    // floor(2√q) is either 2s or 2s + 1 where s = floor(√q).
+    let s = isqrt(q);
+    let r = if s * s + s < q { 2 * s + 1 } else { 2 * s };
+    (q + 1 - r, q + 1 + r)
+}
+
+/// The cofactor ```h = group_order / r``` of a subgroup of prime order ```r```. ```r``` must divide ```group_order```.
+///
+/// Multiplying any point by ```h``` gives a point in the subgroup of order ```r``` (see ```RationalPoint::clear_cofactor```),
+/// which rules out small-subgroup attacks on ECDH.
+pub fn cofactor(group_order: u64, r: u64) -> u64 {
+    assert!(
+        r != 0 && gcd(group_order, r) == r,
+        "r must divide the group order"
+    );
+    group_order / r
+}
+
+/// Check whether ```order``` lies in the Hasse interval for ```F_q```.
+///
+/// Since the demo works over ```F_(p^2)```, set ```q = p^2``` there.
+pub fn check_hasse(order: u64, q: u64) -> bool {
+    let (lower, upper) = hasse_interval(q);
+    lower <= order && order <= upper
+}
+
+/// Check the group laws of ```y^2 = x^3 + ax + b``` over ```F_(p^2)```. See ```EllipticCurve::verify_group_laws```.
+pub fn verify_group_laws<T: PrimeField + Field + fmt::Display>(
+    a: Complex<T>,
+    b: Complex<T>,
+) -> Result<(), String> {
+    EllipticCurve::new(a, b).verify_group_laws()
+}
+
+/// A rational point together with the elliptic curve it lies on.
+///
+/// Since the curve is carried with the point, the scalar multiplication can be written as ```n * point```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CurvePoint<'a, T> {
+    curve: &'a EllipticCurve<T>,
+    point: RationalPoint<T>,
+}
+
+impl<'a, T: Copy> CurvePoint<'a, T> {
+    /// The underlying rational point.
+    pub fn point(&self) -> RationalPoint<T> {
+        self.point
+    }
+
+    /// The curve the point lies on.
+    pub fn curve(&self) -> &'a EllipticCurve<T> {
+        self.curve
+    }
+}
+
+/// Implementation of ```Display```.
+impl<'a, T: fmt::Display> fmt::Display for CurvePoint<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.point)
+    }
+}
+
+/// Implementation of ```Mul```.
+impl<'a, T: Field> Mul<CurvePoint<'a, T>> for u64 {
+    type Output = CurvePoint<'a, T>;
+    /// Overloading the operator ```*```, i.e. the scalar multiplication ```nP```.
+    fn mul(self, rhs: CurvePoint<'a, T>) -> CurvePoint<'a, T> {
+        CurvePoint {
+            curve: rhs.curve,
+            point: rhs.curve.mul(&rhs.point, self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+    use alloc::string::ToString;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    type F = ModInt<863>;
+    type C = Complex<F>;
+
+    fn c(re: u64, im: u64) -> C {
+        Complex::new(F::new(re), F::new(im))
+    }
+
+    /// The curve ```y^2 = x^3 + 2x + 3``` over ```F_(863^2)```.
+    fn curve() -> EllipticCurve<C> {
+        EllipticCurve::new(c(2, 0), c(3, 0))
+    }
+
+    #[test]
+    fn curve_arithmetic_matches_free_formulas() {
+        let curve = curve();
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let p = RationalPoint::random_point(curve.a(), curve.b(), &mut rng);
+            let q = RationalPoint::random_point(curve.a(), curve.b(), &mut rng);
+            assert_eq!(curve.add(&p, &q), p.add_rational_points(&q, curve.a()));
+            assert_eq!(curve.add(&p, &p), p.add_rational_points(&p, curve.a()));
+            assert_eq!(
+                curve.mul(&p, 1000),
+                p.multiply_rational_point(curve.a(), 1000)
+            );
+        }
+    }
+
+    #[test]
+    fn scalar_times_curve_point_matches_multiply() {
+        let curve = curve();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let p = RationalPoint::random_point(curve.a(), curve.b(), &mut rng);
+        let mut sum = RationalPoint::O;
+        for n in 0..50u64 {
+            let np = n * curve.point(p);
+            assert_eq!(np.point(), p.multiply_rational_point(curve.a(), n));
+            assert_eq!(np.point(), sum);
+            assert_eq!(np.curve(), &curve);
+            sum = sum.add_rational_points(&p, curve.a());
+        }
+    }
+
+    #[test]
+    fn count_points_lies_in_hasse_bound() {
+        let order = curve().count_points();
+        let q = 863 * 863;
+        assert!((order as i64 - (q + 1) as i64).abs() <= 2 * 863);
+    }
+
+    #[test]
+    fn count_points_matches_brute_force() {
+        type C11 = Complex<ModInt<11>>;
+        let curve = EllipticCurve::new(
+            Complex::new(ModInt::new(1), ModInt::new(2)),
+            Complex::new(ModInt::new(3), ModInt::new(0)),
+        );
+        let elements: Vec<C11> = (0..11 * 11)
+            .map(|n| Complex::new(ModInt::new(n / 11), ModInt::new(n % 11)))
+            .collect();
+        let mut count = 1;
+        for &x in &elements {
+            for &y in &elements {
+                if y * y == x * x * x + curve.a() * x + curve.b() {
+                    count += 1;
+                }
+            }
+        }
+        assert_eq!(curve.count_points(), count);
+    }
+
+    #[test]
+    fn hasse_check_with_default_parameters() {
+        // The demo works over F_(p^2) with the default p = 863, where 2√q = 2p exactly.
+        let q = 863 * 863;
+        assert_eq!(hasse_interval(q), (q + 1 - 2 * 863, q + 1 + 2 * 863));
+        assert!(check_hasse(curve().count_points(), q));
+        assert!(check_hasse(q + 1 - 2 * 863, q));
+        assert!(!check_hasse(q - 2 * 863, q));
+        assert!(!check_hasse(q + 2 + 2 * 863, q));
+        // For non-square q, 2√q is rounded down.
+        assert_eq!(hasse_interval(863), (806, 922));
+        assert_eq!(hasse_interval(1), (0, 4));
+        let q = (1 << 63) - 25;
+        let (lower, upper) = hasse_interval(q);
+        assert!(lower < q && q + 2 * 3037000499 < upper);
+    }
+
+    #[test]
+    #[should_panic(expected = "q must be in (0, 2^63)")]
+    fn hasse_interval_rejects_too_large_q() {
+        hasse_interval(1 << 63);
+    }
+
+    #[test]
+    fn two_torsion_points_are_roots_of_the_cubic() {
+        type C11 = Complex<ModInt<11>>;
+        let e = |n: u64| Complex::new(ModInt::<11>::new(n), ModInt::new(0));
+        // x^3 + x + 9 = (x - 1)(x - 4)(x - 6) over F_11.
+        let mut points = EllipticCurve::new(e(1), e(9)).torsion_points(2);
+        points.sort_by_key(|p| p.to_string());
+        let mut expected: Vec<RationalPoint<C11>> = [1, 4, 6]
+            .iter()
+            .map(|&x| RationalPoint::Point(e(x), C11::zero()))
+            .collect();
+        expected.push(RationalPoint::O);
+        expected.sort_by_key(|p| p.to_string());
+        assert_eq!(points, expected);
+        // x^3 + x + 4 is irreducible over F_11, hence has no root in F_(11^2).
+        assert_eq!(
+            EllipticCurve::new(e(1), e(4)).torsion_points(2),
+            vec![RationalPoint::O]
+        );
+    }
+
+    #[test]
+    fn full_torsion_has_m_squared_points() {
+        let (a, b) = crate::test_curves::supersingular_curve();
+        let curve = EllipticCurve::new(a, b);
+        assert_eq!(curve.torsion_points(11).len(), 121);
+        assert_eq!(curve.torsion_points(4).len(), 16);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "the right operand is not on the curve")]
+    fn add_rejects_off_curve_operand_in_debug_builds() {
+        let curve = curve();
+        let mut rng = SmallRng::seed_from_u64(2);
+        let p = RationalPoint::random_point(curve.a(), curve.b(), &mut rng);
+        let q = match p {
+            RationalPoint::Point(x, y) => RationalPoint::Point(x, y + c(1, 0)),
+            RationalPoint::O => unreachable!(),
+        };
+        assert!(curve.is_on_curve(&p));
+        curve.add(&p, &q);
+    }
+
+    #[test]
+    fn cofactor_maps_points_into_prime_subgroup() {
+        let curve = curve();
+        let group_order = curve.count_points();
+        let r = crate::util::factorize(group_order)
+            .iter()
+            .map(|&(q, _)| q)
+            .max()
+            .unwrap();
+        let h = cofactor(group_order, r);
+        assert_eq!(h * r, group_order);
+        let mut rng = SmallRng::seed_from_u64(3);
+        for _ in 0..20 {
+            let p = RationalPoint::random_point(curve.a(), curve.b(), &mut rng);
+            let q = p.clear_cofactor(curve.a(), h);
+            assert_eq!(q, curve.mul(&p, h));
+            assert_eq!(curve.mul(&q, r), RationalPoint::O);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "r must divide the group order")]
+    fn cofactor_rejects_non_divisor() {
+        cofactor(100, 7);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn curve_params_cache_a_valid_order() {
+        let curve = curve();
+        let (_, upper) = hasse_interval(863 * 863);
+        let mut rng = SmallRng::seed_from_u64(4);
+        let g = RationalPoint::random_point(curve.a(), curve.b(), &mut rng);
+        let params = CurveParams::new(curve, g, upper).unwrap();
+        let n = params.order();
+        assert!(n <= upper);
+        assert_eq!(curve.count_points() % n, 0);
+        assert_eq!(curve.mul(&g, n), RationalPoint::O);
+        assert_eq!(CurveParams::with_order(curve, g, n), Some(params));
+        assert_eq!(CurveParams::with_order(curve, g, n + 1), None);
+        let off_curve = RationalPoint::Point(c(1, 0), c(1, 0));
+        assert_eq!(CurveParams::new(curve, off_curve, upper), None);
+    }
+
+    #[test]
+    fn group_laws_hold_on_the_default_curve() {
+        // All the 745 000 points or so are examined, which takes several seconds in debug builds.
+        let curve = curve();
+        assert_eq!(verify_group_laws(curve.a(), curve.b()), Ok(()));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::ecdh::KeyError;
+use crate::inverse::InverseError;
+use crate::rational_point::{CurveError, ParsePointError, ScalarError};
+use core::fmt;
+
+/// The errors of the whole crate, so that the errors of different modules can be handled by a single type.
+///
+/// Each module keeps its own error type, which is converted by ```From```, so ```?``` works in a function returning ```Error```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Error {
+    /// An element which had to be inverted is not invertible.
+    NotInvertible,
+    /// A point does not lie on the curve.
+    NotOnCurve,
+    /// A point is the point at infinity ```O``` where an affine point is needed.
+    PointAtInfinity,
+    /// A point does not have the expected order.
+    WrongOrder,
+    /// A scalar is out of the range ```[1, order)```.
+    InvalidScalar,
+    /// A modulus which must be prime is not.
+    CompositeModulus,
+    /// A modulus is too large for the implementation.
+    ModulusTooLarge,
+    /// A string could not be parsed.
+    InvalidFormat,
+}
+
+/// Implementation of ```Display```.
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NotInvertible => write!(f, "an element is not invertible"),
+            Error::NotOnCurve => write!(f, "the point does not lie on the curve"),
+            Error::PointAtInfinity => write!(f, "the point is the point at infinity"),
+            Error::WrongOrder => write!(f, "the point does not have the expected order"),
+            Error::InvalidScalar => write!(f, "the scalar is out of range"),
+            Error::CompositeModulus => write!(f, "the modulus is not prime"),
+            Error::ModulusTooLarge => write!(f, "the modulus is too large"),
+            Error::InvalidFormat => write!(f, "the string is malformed"),
+        }
+    }
+}
+
+/// Implementation of ```Error```.
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Implementation of ```From<InverseError>```. Both of the reasons are ```NotInvertible```.
+impl From<InverseError> for Error {
+    fn from(_: InverseError) -> Self {
+        Error::NotInvertible
+    }
+}
+
+/// Implementation of ```From<CurveError>```.
+impl From<CurveError> for Error {
+    fn from(e: CurveError) -> Self {
+        match e {
+            CurveError::NotInvertible => Error::NotInvertible,
+        }
+    }
+}
+
+/// Implementation of ```From<KeyError>```.
+impl From<KeyError> for Error {
+    fn from(e: KeyError) -> Self {
+        match e {
+            KeyError::PointAtInfinity => Error::PointAtInfinity,
+            KeyError::NotOnCurve => Error::NotOnCurve,
+            KeyError::WrongOrder => Error::WrongOrder,
+        }
+    }
+}
+
+/// Implementation of ```From<ScalarError>```. Both of the reasons are ```InvalidScalar```.
+impl From<ScalarError> for Error {
+    fn from(_: ScalarError) -> Self {
+        Error::InvalidScalar
+    }
+}
+
+/// Implementation of ```From<ParsePointError>```. All the reasons are ```InvalidFormat```.
+impl From<ParsePointError> for Error {
+    fn from(_: ParsePointError) -> Self {
+        Error::InvalidFormat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::dyn_modint::DynModInt;
+    use crate::identities::Identity;
+    use crate::inverse::Inverse;
+    use crate::modint::ModInt;
+    use crate::rational_point::RationalPoint;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    type F = ModInt<863>;
+
+    /// The errors of every module, converted by ```?```.
+    fn errors() -> Vec<Error> {
+        let invert = || -> Result<ModInt<15>, Error> { Ok(ModInt::<15>::new(3).try_inverse()?) };
+        let key = |e: KeyError| -> Result<(), Error> { Err(e)? };
+        let multiply = || -> Result<RationalPoint<F>, Error> {
+            let p = RationalPoint::Point(F::new(3), F::new(6));
+            Ok(p.multiply_checked(F::new(2), 0, 10)?)
+        };
+        let parse = || -> Result<RationalPoint<Complex<F>>, Error> { Ok("(1, 2".parse()?) };
+        let try_add = || -> Result<RationalPoint<ModInt<15>>, Error> {
+            let p = RationalPoint::Point(ModInt::new(1), ModInt::new(3));
+            Ok(p.try_double(ModInt::identity())?)
+        };
+        vec![
+            invert().unwrap_err(),
+            try_add().unwrap_err(),
+            key(KeyError::NotOnCurve).unwrap_err(),
+            key(KeyError::PointAtInfinity).unwrap_err(),
+            key(KeyError::WrongOrder).unwrap_err(),
+            multiply().unwrap_err(),
+            ModInt::<15>::try_new(1).unwrap_err(),
+            DynModInt::try_set_modulus(u64::MAX).unwrap_err(),
+            parse().unwrap_err(),
+        ]
+    }
+
+    #[test]
+    fn every_variant_is_reached_from_its_module() {
+        let errors = errors();
+        assert_eq!(
+            errors,
+            [
+                Error::NotInvertible,
+                Error::NotInvertible,
+                Error::NotOnCurve,
+                Error::PointAtInfinity,
+                Error::WrongOrder,
+                Error::InvalidScalar,
+                Error::CompositeModulus,
+                Error::ModulusTooLarge,
+                Error::InvalidFormat,
+            ]
+        );
+        for e in errors {
+            let described = match e {
+                Error::NotInvertible => "invertible",
+                Error::NotOnCurve => "curve",
+                Error::PointAtInfinity => "infinity",
+                Error::WrongOrder => "order",
+                Error::InvalidScalar => "scalar",
+                Error::CompositeModulus => "prime",
+                Error::ModulusTooLarge => "large",
+                Error::InvalidFormat => "malformed",
+            };
+            assert!(e.to_string().contains(described));
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// Fields, i.e. the arithmetic needed for the coordinates of rational points on elliptic curves.
+///
+/// This is implemented automatically for every type with the supertraits,
+/// e.g. ```ModInt<P>```, ```DynModInt``` and ```Complex<T>``` for such ```T```.
+///
+/// ```
+/// use ecdh_demo_in_rust::complexification::Complex;
+/// use ecdh_demo_in_rust::field::Field;
+/// use ecdh_demo_in_rust::modint::ModInt;
+///
+/// /// ```x / y + y / x```, written only with the bound ```Field```.
+/// fn sum_of_ratios<T: Field>(x: T, y: T) -> Option<T> {
+///     Some(x * y.inverse()? + y * x.inverse()?)
+/// }
+///
+/// let x = ModInt::<863>::new(2);
+/// let y = ModInt::<863>::new(3);
+/// assert_eq!(sum_of_ratios(x, y).unwrap() * x * y, x * x + y * y);
+/// assert_eq!(sum_of_ratios(x, ModInt::new(0)), None);
+///
+/// let z = Complex::new(x, y);
+/// assert_eq!(sum_of_ratios(z, z), Some(Complex::new(ModInt::new(2), ModInt::new(0))));
+/// ```
+pub trait Field:
+    Add<Output = Self>
+    + Mul<Output = Self>
+    + Sub<Output = Self>
+    + Neg<Output = Self>
+    + Copy
+    + Eq
+    + Zero
+    + Identity
+    + Inverse
+{
+}
+
+impl<
+        T: Add<Output = T>
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Neg<Output = T>
+            + Copy
+            + Eq
+            + Zero
+            + Identity
+            + Inverse,
+    > Field for T
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+    use crate::quadratic_extension::QuadraticExtension;
+    use alloc::vec::Vec;
+    use core::fmt::Debug;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    type F = ModInt<863>;
+    type C = Complex<F>;
+
+    /// Check the field axioms on all the triples from ```xs```, ```ys``` and ```zs``` taken elementwise.
+    fn assert_field_axioms<T: Field + Debug>(xs: &[T], ys: &[T], zs: &[T]) {
+        let (zero, one) = (T::zero(), T::identity());
+        for ((&x, &y), &z) in xs.iter().zip(ys.iter()).zip(zs.iter()) {
+            assert_eq!((x + y) + z, x + (y + z));
+            assert_eq!((x * y) * z, x * (y * z));
+            assert_eq!(x + y, y + x);
+            assert_eq!(x * y, y * x);
+            assert_eq!(x * (y + z), x * y + x * z);
+            assert_eq!((x + y) * z, x * z + y * z);
+            assert_eq!(x + zero, x);
+            assert_eq!(x * one, x);
+            assert_eq!(x + -x, zero);
+            assert_eq!(x - y, x + -y);
+            assert_eq!(x * zero, zero);
+            match x.inverse() {
+                Some(inv) => assert_eq!(x * inv, one),
+                None => assert_eq!(x, zero),
+            }
+        }
+    }
+
+    fn random_elements(n: usize, seed: u64) -> Vec<C> {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| Complex::new(F::new(rng.gen_range(0, 863)), F::new(rng.gen_range(0, 863))))
+            .collect()
+    }
+
+    #[test]
+    fn modint_satisfies_field_axioms() {
+        let every: Vec<F> = (0..863).map(F::new).collect();
+        let mut rng = SmallRng::seed_from_u64(0);
+        let ys: Vec<F> = (0..863).map(|_| F::new(rng.gen_range(0, 863))).collect();
+        let zs: Vec<F> = (0..863).map(|_| F::new(rng.gen_range(0, 863))).collect();
+        assert_field_axioms(&every, &ys, &zs);
+        assert_eq!(F::zero().inverse(), None);
+    }
+
+    #[test]
+    fn complex_satisfies_field_axioms() {
+        assert_field_axioms(
+            &random_elements(2000, 1),
+            &random_elements(2000, 2),
+            &random_elements(2000, 3),
+        );
+        let every: Vec<Complex<ModInt<11>>> = (0..11 * 11)
+            .map(|n| Complex::new(ModInt::new(n / 11), ModInt::new(n % 11)))
+            .collect();
+        let mut shifted = every.clone();
+        shifted.rotate_left(7);
+        let mut reversed = every.clone();
+        reversed.reverse();
+        assert_field_axioms(&every, &shifted, &reversed);
+    }
+
+    #[test]
+    fn quadratic_extension_satisfies_field_axioms_for_p_1_mod_4() {
+        // 2 is a quadratic non-residue modulo 13, and 13 % 4 == 1.
+        type Q = QuadraticExtension<ModInt<13>, 2>;
+        let every: Vec<Q> = (0..169)
+            .map(|n| Q::new(ModInt::new(n % 13), ModInt::new(n / 13)))
+            .collect();
+        let mut shifted = every.clone();
+        shifted.rotate_left(5);
+        let mut reversed = every.clone();
+        reversed.reverse();
+        assert_field_axioms(&every, &shifted, &reversed);
+    }
+}
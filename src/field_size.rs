@@ -0,0 +1,27 @@
+use crate::characteristic::Characteristic;
+
+/// Finite fields ```F_q``` with ```q = p^n```,
+/// where ```p``` is the characteristic and ```n``` is the extension degree over the prime field ```F_p```.
+pub trait FieldSize: Characteristic {
+    /// The extension degree ```n``` over the prime field.
+    fn degree() -> u32;
+
+    /// The number of elements ```q = p^n```.
+    fn field_order() -> u64 {
+        Self::characteristic().pow(Self::degree())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+
+    #[test]
+    fn field_order_is_p_to_the_degree() {
+        assert_eq!(ModInt::<863>::field_order(), 863);
+        assert_eq!(Complex::<ModInt<863>>::degree(), 2);
+        assert_eq!(Complex::<ModInt<863>>::field_order(), 863 * 863);
+    }
+}
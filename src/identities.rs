@@ -1,9 +1,80 @@
 /// Zero element. i.e. ```0``` in the ring of integers.
 pub trait Zero {
     fn zero() -> Self;
+
+    /// Check whether it is equal to ```zero()```.
+    fn is_zero(&self) -> bool
+    where
+        Self: Sized + PartialEq,
+    {
+        *self == Self::zero()
+    }
 }
 
 /// Identity element. i.e. ```1``` in the ring of integers.
 pub trait Identity {
     fn identity() -> Self;
+
+    /// Check whether it is equal to ```identity()```.
+    fn is_one(&self) -> bool
+    where
+        Self: Sized + PartialEq,
+    {
+        *self == Self::identity()
+    }
+}
+
+/// Implementation of ```Zero``` for the product ring ```A × B```.
+impl<A: Zero, B: Zero> Zero for (A, B) {
+    /// A function that returns ```(0, 0)```.
+    fn zero() -> Self {
+        (A::zero(), B::zero())
+    }
+}
+
+/// Implementation of ```Identity``` for the product ring ```A × B```.
+impl<A: Identity, B: Identity> Identity for (A, B) {
+    /// A function that returns ```(1, 1)```.
+    fn identity() -> Self {
+        (A::identity(), B::identity())
+    }
+}
+
+/// ```Identity``` under the usual name of the multiplicative identity.
+pub use self::Identity as One;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+    use crate::polynomial::Polynomial;
+
+    type F = ModInt<863>;
+
+    #[test]
+    fn zero_and_one_predicates() {
+        assert!(F::new(0).is_zero() && !F::new(0).is_one());
+        assert!(F::new(864).is_one() && !F::new(864).is_zero());
+        assert!(!F::new(862).is_zero() && !F::new(862).is_one());
+        let i = Complex::new(F::new(0), F::new(1));
+        assert!(!i.is_zero() && !i.is_one());
+        assert!((i * i * i * i).is_one());
+        assert!((i - i).is_zero());
+        let f = Polynomial::new(&[F::new(1), F::new(0)]);
+        assert!(f.is_one());
+        assert!((f.clone() - f).is_zero());
+        assert!(!Polynomial::new(&[F::new(0), F::new(1)]).is_one());
+        assert_eq!(<F as One>::identity(), F::new(1));
+    }
+
+    #[test]
+    fn tuples_form_product_rings() {
+        let zero = <(ModInt<7>, ModInt<11>)>::zero();
+        let one = <(ModInt<7>, ModInt<11>)>::identity();
+        assert_eq!(zero, (ModInt::new(0), ModInt::new(0)));
+        assert_eq!(one, (ModInt::new(1), ModInt::new(1)));
+        assert!(zero.is_zero() && one.is_one());
+        assert!(!(ModInt::<7>::new(0), ModInt::<11>::new(1)).is_one());
+    }
 }
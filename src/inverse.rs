@@ -1,7 +1,60 @@
+use crate::identities::Zero;
+use core::fmt;
+
 /// Inverse element. If ```x``` has its inverse, this function returns ```Some(x^(-1))```, otherwise ```None```.
 pub trait Inverse
 where
-    Self: std::marker::Sized,
+    Self: core::marker::Sized,
 {
     fn inverse(self) -> Option<Self>;
+
+    /// The same as ```inverse```, but it returns the reason why the inversion failed.
+    fn try_inverse(self) -> Result<Self, InverseError>
+    where
+        Self: Zero + PartialEq,
+    {
+        if self == Self::zero() {
+            return Err(InverseError::Zero);
+        }
+        self.inverse().ok_or(InverseError::NotCoprime)
+    }
+}
+
+/// Reasons why an inversion fails.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum InverseError {
+    /// The element is ```0```.
+    Zero,
+    /// The element is not ```0``` but not coprime to the modulus, e.g. ```3``` in ```Z / (15)Z```.
+    NotCoprime,
+}
+
+/// Implementation of ```Display```.
+impl fmt::Display for InverseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InverseError::Zero => write!(f, "zero is not invertible"),
+            InverseError::NotCoprime => write!(f, "the element is not coprime to the modulus"),
+        }
+    }
+}
+
+/// Implementation of ```Error```.
+#[cfg(feature = "std")]
+impl std::error::Error for InverseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modint::ModInt;
+
+    #[test]
+    fn try_inverse_distinguishes_failures() {
+        type G = ModInt<15>;
+        assert_eq!(G::new(0).try_inverse(), Err(InverseError::Zero));
+        assert_eq!(G::new(3).try_inverse(), Err(InverseError::NotCoprime));
+        assert_eq!(G::new(10).try_inverse(), Err(InverseError::NotCoprime));
+        assert_eq!(G::new(2).try_inverse(), Ok(G::new(8)));
+        assert_eq!(ModInt::<863>::new(0).try_inverse(), Err(InverseError::Zero));
+    }
 }
@@ -0,0 +1,124 @@
+use crate::field::Field;
+use crate::rational_point::RationalPoint;
+use alloc::vec::Vec;
+
+/// The quantities ```(x_Q, y_Q, g^x_Q, g^y_Q, v_Q, u_Q)``` of Vélu's formulas
+/// for every ```Q``` in the kernel except ```O```, taking only one of ```Q``` and ```-Q```.
+fn velu_terms<T: Field>(kernel: &[RationalPoint<T>], a: T) -> Vec<(T, T, T, T, T, T)> {
+    let id = T::identity();
+    let two = id + id;
+    let three = two + id;
+    let mut terms: Vec<(T, T, T, T, T, T)> = Vec::new();
+    for q in kernel {
+        let (xq, yq) = match q.affine() {
+            Some(coordinates) => coordinates,
+            None => continue,
+        };
+        if terms.iter().any(|&(x, _, _, _, _, _)| x == xq) {
+            continue;
+        }
+        let gx = three * xq * xq + a;
+        let gy = -two * yq;
+        // A point of order 2 is counted once, and the other ones twice for Q and -Q.
+        let v = if yq == T::zero() { gx } else { two * gx };
+        let u = gy * gy;
+        terms.push((xq, yq, gx, gy, v, u));
+    }
+    terms
+}
+
+/// Evaluate the isogeny from ```y^2 = x^3 + ax + b``` with the finite subgroup ```kernel``` as its kernel at ```p```
+/// by Vélu's formulas, where ```kernel``` lists all the points of the subgroup (```O``` may be omitted).
+///
+/// It returns the image of ```p``` and the coefficients ```(a - 5v, b - 7w)``` of the image curve, where
+/// ```v = Σ v_Q``` and ```w = Σ (u_Q + x_Q v_Q)```.
+/// For ```p = (x, y)``` not in the kernel the image is
+/// ```(x + Σ (v_Q / (x - x_Q) + u_Q / (x - x_Q)^2), y - Σ (u_Q 2y / (x - x_Q)^3 + v_Q (y - y_Q) / (x - x_Q)^2 - g^x_Q g^y_Q / (x - x_Q)^2))```,
+/// and the points of the kernel go to ```O```. The degree of the isogeny is the number of the points of the kernel.
+pub fn evaluate_isogeny<T: Field>(
+    kernel: &[RationalPoint<T>],
+    p: &RationalPoint<T>,
+    a: T,
+    b: T,
+) -> (RationalPoint<T>, (T, T)) {
+    let id = T::identity();
+    let two = id + id;
+    let five = two + two + id;
+    let seven = five + two;
+    let terms = velu_terms(kernel, a);
+    let mut v = T::zero();
+    let mut w = T::zero();
+    for &(xq, _, _, _, vq, uq) in &terms {
+        v = v + vq;
+        w = w + uq + xq * vq;
+    }
+    let image_curve = (a - five * v, b - seven * w);
+    let (x, y) = match p.affine() {
+        Some(coordinates) => coordinates,
+        None => return (RationalPoint::O, image_curve),
+    };
+    let mut image_x = x;
+    let mut image_y = y;
+    for &(xq, yq, gx, gy, vq, uq) in &terms {
+        let d_inv = match (x - xq).inverse() {
+            Some(d_inv) => d_inv,
+            // x = x_Q, i.e. p = Q or -Q lies in the kernel.
+            None => return (RationalPoint::O, image_curve),
+        };
+        let d_inv2 = d_inv * d_inv;
+        image_x = image_x + vq * d_inv + uq * d_inv2;
+        image_y =
+            image_y - (uq * two * y * d_inv2 * d_inv + vq * (y - yq) * d_inv2 - gx * gy * d_inv2);
+    }
+    (RationalPoint::Point(image_x, image_y), image_curve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identities::Zero;
+    use crate::inverse::Inverse;
+    use crate::test_curves::{random_points, supersingular_curve, torsion_points, C};
+
+    /// The multiples ```0P, 1P, ..., (l - 1)P``` of a point ```P``` of order ```l```.
+    fn subgroup(p: &RationalPoint<C>, a: C, l: u64) -> Vec<RationalPoint<C>> {
+        (0..l).map(|k| p.multiply_rational_point(a, k)).collect()
+    }
+
+    #[test]
+    fn isogeny_composed_with_dual_is_multiplication_by_degree() {
+        let (a, b) = supersingular_curve();
+        let mut points = torsion_points(0);
+        let p = points.next().unwrap();
+        let kernel = subgroup(&p, a, 11);
+        let q = points.find(|q| !kernel.contains(q)).unwrap();
+        // The dual of the isogeny with the kernel <P> has the kernel φ(E[11]) = <φ(Q)>,
+        // and the composition is [11] followed by an isomorphism (x, y) -> (t x, s y) onto the Vélu model of E.
+        let (_, (a1, b1)) = evaluate_isogeny(&kernel, &RationalPoint::O, a, b);
+        let (q1, _) = evaluate_isogeny(&kernel, &q, a, b);
+        assert_ne!(q1, RationalPoint::O);
+        let dual_kernel = subgroup(&q1, a1, 11);
+        let (_, (a2, b2)) = evaluate_isogeny(&dual_kernel, &RationalPoint::O, a1, b1);
+        let mut scaling: Option<(C, C)> = None;
+        for r in random_points(1).take(50) {
+            let (r1, _) = evaluate_isogeny(&kernel, &r, a, b);
+            assert!(r1.is_on_curve(a1, b1));
+            let (r2, _) = evaluate_isogeny(&dual_kernel, &r1, a1, b1);
+            let expected = r.multiply_rational_point(a, 11);
+            let ((x, y), (x2, y2)) = match (expected.affine(), r2.affine()) {
+                (Some(xy), Some(xy2)) => (xy, xy2),
+                (None, None) => continue,
+                _ => panic!("11{} and its image through the isogenies differ", r),
+            };
+            if x == C::zero() || y == C::zero() {
+                continue;
+            }
+            let (t, s) =
+                *scaling.get_or_insert((x2 * x.inverse().unwrap(), y2 * y.inverse().unwrap()));
+            assert_eq!((x2, y2), (t * x, s * y));
+        }
+        let (t, s) = scaling.unwrap();
+        assert_eq!(t * t * t, s * s);
+        assert_eq!((a2, b2), (t * t * a, t * t * t * b));
+    }
+}
@@ -0,0 +1,34 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod characteristic;
+pub mod complexification;
+pub mod ct_eq;
+#[cfg(all(feature = "std", feature = "kdf"))]
+pub mod demo;
+pub mod dyn_modint;
+pub mod ecdh;
+pub mod edwards;
+pub mod elliptic_curve;
+pub mod error;
+pub mod field;
+pub mod field_size;
+pub mod identities;
+pub mod inverse;
+pub mod isogeny;
+pub mod modint;
+pub mod montgomery;
+pub mod pairing;
+pub mod polynomial;
+pub mod prime_field;
+pub mod prime_field_extension;
+pub mod projective_point;
+pub mod quadratic_extension;
+pub mod rational_point;
+#[cfg(feature = "kdf")]
+pub mod sha256;
+pub mod signed;
+#[cfg(test)]
+mod test_curves;
+pub mod util;
@@ -1,11 +1,62 @@
 use crate::characteristic::Characteristic;
+use crate::ct_eq::{ct_eq_u64, ct_select_u64, ConditionallySelectable, ConstantTimeEq};
+use crate::error::Error;
+use crate::field_size::FieldSize;
 use crate::identities::{Identity, Zero};
 use crate::inverse::Inverse;
-use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::prime_field::PrimeField;
+use crate::signed::Signed;
+use crate::util::{factorize, is_prime, mod_inverse, reduce_exponent};
+use alloc::string::{String, ToString};
+use core::array::TryFromSliceError;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
 
-/// Elements of ```Z / (MOD)Z```.
+/// Construct an element of ```Z / (MOD)Z```, i.e. ```modint!(P; n)``` is ```ModInt::<P>::new(n)```.
+/// The modulus can be omitted as ```modint!(n)``` if it is inferred.
+/// ```text
+/// let x = modint!(863; 5);
+/// ```
+#[macro_export]
+macro_rules! modint {
+    ($p:expr; $n:expr) => {
+        $crate::modint::ModInt::<{ $p }>::new($n)
+    };
+    ($n:expr) => {
+        $crate::modint::ModInt::new($n)
+    };
+}
+
+/// A token showing that ```MOD``` is prime, so that ```Z / (MOD)Z``` is a field.
+///
+/// The Miller–Rabin test runs only once in ```new```,
+/// and the elements made by ```element``` afterwards need no further check of the modulus.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PrimeModulus<const MOD: u64> {
+    _private: (),
+}
+
+impl<const MOD: u64> PrimeModulus<MOD> {
+    /// Constructor, which fails with ```Error::CompositeModulus``` unless ```MOD``` is prime.
+    pub fn new() -> Result<Self, Error> {
+        if !is_prime(MOD) {
+            return Err(Error::CompositeModulus);
+        }
+        Ok(Self { _private: () })
+    }
+
+    /// The element ```n mod MOD``` of the field ```Z / (MOD)Z```.
+    pub fn element(&self, n: u64) -> ModInt<MOD> {
+        ModInt::new(n)
+    }
+}
+
+/// Elements of ```Z / (MOD)Z```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct ModInt<const MOD: u64> {
     representative: u64,
 }
@@ -18,29 +69,158 @@ impl<const MOD: u64> ModInt<MOD> {
         }
     }
 
+    /// Constructor without the reduction modulo ```MOD```, which saves a division in hot loops.
+    ///
+    /// The caller must guarantee ```n < MOD```, which is checked only in debug builds.
+    /// Otherwise ```Eq``` and ```Hash``` no longer agree with the equality in ```Z / (MOD)Z```, see ```canonicalize```.
+    pub fn new_unchecked(n: u64) -> Self {
+        debug_assert!(n < MOD);
+        ModInt { representative: n }
+    }
+
+    /// Constructor checking that ```MOD``` is prime, so that ```Z / (MOD)Z``` is a field as almost every algorithm assumes.
+    ///
+    /// The primality test runs on every call, so use ```PrimeModulus``` to construct many elements.
+    pub fn try_new(n: u64) -> Result<Self, Error> {
+        Ok(PrimeModulus::<MOD>::new()?.element(n))
+    }
+
+    /// Panic unless ```MOD``` is prime. Call it once before using ```ModInt<MOD>``` as a field.
+    pub fn assert_prime_modulus() {
+        assert!(is_prime(MOD), "the modulus {} is not prime", MOD);
+    }
+
+    /// The same element with the representative reduced modulo ```MOD```.
+    /// Every object is kept reduced by the constructors and the operators, so this is the identity for them;
+    /// ```Eq``` and ```Hash``` compare the representatives and rely on it.
+    pub fn canonicalize(&self) -> Self {
+        Self::new(self.representative)
+    }
+
     /// Make it ```u64``` type.
     pub fn to_int(&self) -> u64 {
         self.representative
     }
 
+    /// The centered representative in ```(-MOD/2, MOD/2]```, e.g. ```-1``` instead of ```MOD - 1```.
+    pub fn to_signed(&self) -> i64 {
+        if self.representative > MOD / 2 {
+            -((MOD - self.representative) as i64)
+        } else {
+            self.representative as i64
+        }
+    }
+
+    /// The centered representative as a string, see ```to_signed```.
+    pub fn to_signed_string(&self) -> String {
+        self.to_signed().to_string()
+    }
+
+    /// The representative in little-endian bytes.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        self.representative.to_le_bytes()
+    }
+
+    /// Make it from little-endian bytes, reducing the integer modulo ```MOD```.
+    pub fn from_bytes(b: [u8; 8]) -> Self {
+        Self::new(u64::from_le_bytes(b))
+    }
+
+    /// Reduce the representative in ```[0, MOD)``` modulo ```NEW```, e.g. ```5``` in ```Z / (863)Z``` to ```5``` in ```Z / (7)Z```.
+    ///
+    /// This is an operation on representatives, which is not a ring homomorphism unless ```NEW``` divides ```MOD```.
+    pub fn reduce_into<const NEW: u64>(&self) -> ModInt<NEW> {
+        ModInt::new(self.representative)
+    }
+
     /// Culculate exponentiation by repeated squaring.
+    ///
+    /// The products are taken by ```*```, which computes in ```u128```, so that it works for every ```MOD < 2^64```.
     pub fn power(&self, n: u64) -> Self {
-        let mut res = 1;
-        let mut a = self.representative;
+        let mut res = Self::identity();
+        let mut a = *self;
         let mut m = n;
         loop {
             if m == 0 {
                 break;
             }
             if m % 2 == 1 {
-                res = (res * a) % MOD;
+                res *= a;
             }
-            a = (a * a) % MOD;
-            m = m / 2;
+            a *= a;
+            m /= 2;
         }
-        ModInt {
-            representative: res,
+        res
+    }
+
+    /// Exponentiation by an exponent of any size given as little-endian 64-bit limbs,
+    /// i.e. ```self^e``` for ```e = Σ exp_limbs[i] 2^(64i)```, by square-and-multiply over the bits from the top.
+    /// The empty slice means ```e = 0```.
+    pub fn power_bits(&self, exp_limbs: &[u64]) -> Self {
+        let mut res = Self::identity();
+        for &limb in exp_limbs.iter().rev() {
+            for i in (0..64).rev() {
+                res = res * res;
+                if (limb >> i) & 1 == 1 {
+                    res *= *self;
+                }
+            }
         }
+        res
+    }
+
+    /// The same as ```power```, but ```n``` is reduced by ```reduce_exponent``` first,
+    /// where ```order``` is a multiple of the multiplicative order of ```self```, e.g. ```MOD - 1``` for prime ```MOD```.
+    pub fn power_with_order(&self, n: u64, order: u64) -> Self {
+        self.power(reduce_exponent(n, order))
+    }
+
+    /// The sum computed from the representatives in ```u64``` as ```+``` does,
+    /// or ```None``` if ```self.to_int() + rhs.to_int()``` overflows, which is possible only for ```MOD > 2^63```.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let sum = self.representative.checked_add(rhs.representative)?;
+        Some(ModInt {
+            representative: sum % MOD,
+        })
+    }
+
+    /// The product computed from the representatives in ```u64``` without widening,
+    /// or ```None``` if ```self.to_int() * rhs.to_int()``` overflows, which is possible only for ```MOD > 2^32```.
+    ///
+    /// ```*``` computes in ```u128``` and never overflows,
+    /// but this tells whether the product would also fit in ```u64``` arithmetic without widening.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let product = self.representative.checked_mul(rhs.representative)?;
+        Some(ModInt {
+            representative: product % MOD,
+        })
+    }
+
+    /// The multiplicative order, i.e. the smallest ```k > 0``` with ```self^k = 1```,
+    /// or ```None``` for ```0```. This function works well when ```MOD``` is prime.
+    ///
+    /// Starting from ```k = MOD - 1```, the prime factors of ```MOD - 1``` are removed from ```k```
+    /// as long as ```self^k = 1``` still holds.
+    pub fn multiplicative_order(&self) -> Option<u64> {
+        if self.representative == 0 {
+            return None;
+        }
+        let mut k = MOD - 1;
+        for (q, e) in factorize(MOD - 1) {
+            for _ in 0..e {
+                if self.power(k / q).representative == 1 {
+                    k /= q;
+                } else {
+                    break;
+                }
+            }
+        }
+        Some(k)
+    }
+
+    /// Check whether it generates the multiplicative group, i.e. its order is ```MOD - 1```.
+    pub fn is_primitive_root(&self) -> bool {
+        self.multiplicative_order() == Some(MOD - 1)
     }
 }
 
@@ -51,13 +231,64 @@ impl<const MOD: u64> fmt::Display for ModInt<MOD> {
     }
 }
 
+/// Implementation of ```Distribution``` for ```Standard```, i.e. ```rng.gen::<ModInt<MOD>>()```.
+/// The elements are sampled uniformly from the whole ```Z / (MOD)Z``` including ```0```.
+impl<const MOD: u64> Distribution<ModInt<MOD>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ModInt<MOD> {
+        ModInt::new(rng.gen_range(0, MOD))
+    }
+}
+
+/// The smallest primitive root of ```Z / (MOD)Z```, i.e. a generator of the multiplicative group.
+/// ```MOD``` must be prime, otherwise this function may not terminate.
+pub fn find_primitive_root<const MOD: u64>() -> ModInt<MOD> {
+    let mut g = ModInt::new(1);
+    while !g.is_primitive_root() {
+        g.representative += 1;
+    }
+    g
+}
+
+/// All the elements ```0, 1, ..., MOD - 1``` of ```Z / (MOD)Z```.
+pub fn all<const MOD: u64>() -> impl Iterator<Item = ModInt<MOD>> {
+    (0..MOD).map(ModInt::new_unchecked)
+}
+
+/// Implementation of ```Hash```.
+/// It is checked in debug builds that the representative is reduced, so that equal elements have equal hashes.
+impl<const MOD: u64> Hash for ModInt<MOD> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        debug_assert!(self.representative < MOD);
+        self.representative.hash(state);
+    }
+}
+
+/// Implementation of ```PartialEq<u64>```, comparing with ```ModInt::new(n)```, e.g. ```ModInt::<863>::new(0) == 863```.
+impl<const MOD: u64> PartialEq<u64> for ModInt<MOD> {
+    fn eq(&self, other: &u64) -> bool {
+        *self == ModInt::new(*other)
+    }
+}
+
+/// Implementation of ```TryFrom<&[u8]>```, i.e. ```from_bytes``` for slices.
+/// It fails unless the slice has exactly 8 bytes.
+impl<const MOD: u64> TryFrom<&[u8]> for ModInt<MOD> {
+    type Error = TryFromSliceError;
+    fn try_from(b: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bytes(b.try_into()?))
+    }
+}
+
 /// Implementation of ```Add```.
 impl<const MOD: u64> Add for ModInt<MOD> {
     type Output = Self;
     /// Overloading the operator ```+```.
+    /// The sum is taken with overflow and ```MOD``` is subtracted through a mask if it carried or is not below ```MOD```,
+    /// so that it neither overflows as ```(self + rhs) % MOD``` may for large moduli nor branches on the operands.
     fn add(self, rhs: Self) -> Self::Output {
+        let (r, carry) = self.representative.overflowing_add(rhs.representative);
         ModInt {
-            representative: (self.representative + rhs.representative) % MOD,
+            representative: r.wrapping_sub(MOD & 0u64.wrapping_sub((carry | (r >= MOD)) as u64)),
         }
     }
 }
@@ -66,9 +297,7 @@ impl<const MOD: u64> Add for ModInt<MOD> {
 impl<const MOD: u64> AddAssign for ModInt<MOD> {
     /// Overloading the operator ```+=```.
     fn add_assign(&mut self, other: Self) {
-        *self = Self {
-            representative: (self.representative + other.representative) % MOD,
-        };
+        *self = *self + other;
     }
 }
 
@@ -76,9 +305,13 @@ impl<const MOD: u64> AddAssign for ModInt<MOD> {
 impl<const MOD: u64> Sub for ModInt<MOD> {
     type Output = Self;
     /// Overloading the operator ```-```.
+    /// The difference is taken with wrapping and ```MOD``` is added back through a mask if it borrowed,
+    /// so that it neither overflows as ```self + MOD - rhs``` may for large moduli nor branches on the operands.
     fn sub(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.representative, rhs.representative);
+        let r = a.wrapping_sub(b);
         ModInt {
-            representative: (self.representative + MOD - rhs.representative) % MOD,
+            representative: r.wrapping_add(MOD & 0u64.wrapping_sub((a < b) as u64)),
         }
     }
 }
@@ -87,9 +320,7 @@ impl<const MOD: u64> Sub for ModInt<MOD> {
 impl<const MOD: u64> SubAssign for ModInt<MOD> {
     /// Overloading the operator ```-=```.
     fn sub_assign(&mut self, other: Self) {
-        *self = Self {
-            representative: (self.representative + MOD - other.representative) % MOD,
-        };
+        *self = *self - other;
     }
 }
 
@@ -97,9 +328,11 @@ impl<const MOD: u64> SubAssign for ModInt<MOD> {
 impl<const MOD: u64> Mul for ModInt<MOD> {
     type Output = Self;
     /// Overloading the operator ```*```.
+    /// The product is computed in ```u128```, so that it does not overflow for large moduli.
     fn mul(self, rhs: Self) -> Self::Output {
+        let product = self.representative as u128 * rhs.representative as u128;
         ModInt {
-            representative: (self.representative * rhs.representative) % MOD,
+            representative: (product % MOD as u128) as u64,
         }
     }
 }
@@ -108,18 +341,20 @@ impl<const MOD: u64> Mul for ModInt<MOD> {
 impl<const MOD: u64> MulAssign for ModInt<MOD> {
     /// Overloading the operator ```*=```.
     fn mul_assign(&mut self, other: Self) {
-        *self = Self {
-            representative: (self.representative * other.representative) % MOD,
-        };
+        *self = *self * other;
     }
 }
 
 /// Implementation of ```Neg```.
 impl<const MOD: u64> Neg for ModInt<MOD> {
     type Output = Self;
-    /// Overloading the operator ```-```.
+    /// Overloading the operator ```-```, i.e. ```0 - self``` with the same masking as ```Sub```.
     fn neg(self) -> Self {
-        ModInt::<MOD>::new(MOD - self.representative)
+        let a = self.representative;
+        let r = 0u64.wrapping_sub(a);
+        ModInt {
+            representative: r.wrapping_add(MOD & 0u64.wrapping_sub((a != 0) as u64)),
+        }
     }
 }
 
@@ -131,6 +366,13 @@ impl<const MOD: u64> Zero for ModInt<MOD> {
     }
 }
 
+/// Implementation of ```Default```, returning ```0```.
+impl<const MOD: u64> Default for ModInt<MOD> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
 /// Implementation of ```Identity``` defined in ```identities.rs```.
 impl<const MOD: u64> Identity for ModInt<MOD> {
     /// A function that returns an object corresponding to ```1``` in ```Z / (MOD)Z```.
@@ -139,18 +381,37 @@ impl<const MOD: u64> Identity for ModInt<MOD> {
     }
 }
 
+/// Implementation of ```ConstantTimeEq``` defined in ```ct_eq.rs```.
+impl<const MOD: u64> ConstantTimeEq for ModInt<MOD> {
+    fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq_u64(self.representative, other.representative)
+    }
+}
+
+/// Implementation of ```ConditionallySelectable``` defined in ```ct_eq.rs```.
+impl<const MOD: u64> ConditionallySelectable for ModInt<MOD> {
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        Self {
+            representative: ct_select_u64(a.representative, b.representative, choice),
+        }
+    }
+}
+
+/// Implementation of ```Signed``` defined in ```signed.rs```.
+impl<const MOD: u64> Signed for ModInt<MOD> {
+    /// The elements whose centered representatives (see ```to_signed```) are negative.
+    fn is_negative(&self) -> bool {
+        self.to_signed() < 0
+    }
+}
+
 /// Implementation of ```Inverse``` defined in ```inverse.rs```.
 impl<const MOD: u64> Inverse for ModInt<MOD> {
     /// A function that returns an object corresponding to ```x^(-1)``` in ```Z / (MOD)Z```.
-    /// This function works well when ```MOD``` is prime.
+    /// It is computed by the extended Euclidean algorithm rather than as ```x^(MOD - 2)```,
+    /// so that it is correct also for composite ```MOD```, where it returns ```None``` unless ```gcd(x, MOD) = 1```.
     fn inverse(self) -> Option<ModInt<MOD>> {
-        let n = self.to_int();
-        if num::Integer::gcd(&n, &MOD) != 1 {
-            None
-        } else {
-            let ret = self.power(MOD - 2);
-            Some(ret)
-        }
+        mod_inverse(self.representative, MOD).map(|n| ModInt { representative: n })
     }
 }
 
@@ -161,3 +422,347 @@ impl<const MOD: u64> Characteristic for ModInt<MOD> {
         MOD
     }
 }
+
+/// Implementation of ```FieldSize``` defined in ```field_size.rs```.
+impl<const MOD: u64> FieldSize for ModInt<MOD> {
+    /// ```Z / (MOD)Z``` is the prime field itself.
+    fn degree() -> u32 {
+        1
+    }
+}
+
+/// Implementation of ```PrimeField``` defined in ```prime_field.rs```.
+/// This works well when ```MOD``` is prime.
+impl<const MOD: u64> PrimeField for ModInt<MOD> {
+    fn from_int(n: u64) -> Self {
+        ModInt::new(n)
+    }
+
+    fn to_int(&self) -> u64 {
+        self.representative
+    }
+}
+
+/// Implementation of ```num_traits::Zero```, delegating to ```Zero``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl<const MOD: u64> num_traits::Zero for ModInt<MOD> {
+    fn zero() -> Self {
+        <Self as Zero>::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.representative == 0
+    }
+}
+
+/// Implementation of ```num_traits::One```, delegating to ```Identity``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl<const MOD: u64> num_traits::One for ModInt<MOD> {
+    fn one() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    type F = ModInt<863>;
+
+    /// A generic function which only knows the bounds from ```num_traits```.
+    #[cfg(feature = "num-traits")]
+    fn sum_of_squares<T: num_traits::Zero + num_traits::One + Mul<Output = T> + Copy>(
+        xs: &[T],
+    ) -> (T, bool) {
+        let s = xs.iter().fold(T::zero(), |acc, &x| acc + x * x);
+        (s, (s * T::one()).is_zero())
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn num_traits_generic_function_works_on_modint() {
+        let xs = [F::new(3), F::new(4), F::new(5)];
+        assert_eq!(sum_of_squares(&xs), (F::new(50), false));
+        assert_eq!(sum_of_squares::<F>(&[]), (F::new(0), true));
+        assert_eq!(
+            sum_of_squares(&[F::new(1), F::new(862)]),
+            (F::new(2), false)
+        );
+    }
+
+    #[test]
+    fn internal_identities_agree_with_constructors() {
+        assert_eq!(<F as Zero>::zero(), F::new(863));
+        assert_eq!(F::identity(), F::new(864));
+    }
+
+    #[test]
+    fn bytes_round_trip_and_reduce() {
+        for n in [0, 1, 500, 862] {
+            let x = F::new(n);
+            assert_eq!(F::from_bytes(x.to_bytes()), x);
+            assert_eq!(F::try_from(&x.to_bytes()[..]).unwrap(), x);
+        }
+        assert_eq!(F::from_bytes(863u64.to_le_bytes()), F::new(0));
+        assert_eq!(
+            F::from_bytes(u64::MAX.to_le_bytes()),
+            F::new(u64::MAX % 863)
+        );
+        assert!(F::try_from(&[0u8; 7][..]).is_err());
+        assert!(F::try_from(&[0u8; 9][..]).is_err());
+    }
+
+    #[test]
+    fn signed_representative_is_centered() {
+        assert_eq!(F::new(0).to_signed(), 0);
+        assert_eq!(F::new(431).to_signed(), 431);
+        assert_eq!(F::new(432).to_signed(), -431);
+        assert_eq!(F::new(862).to_signed(), -1);
+        assert_eq!(F::new(862).to_signed_string(), "-1");
+        assert_eq!(ModInt::<10>::new(5).to_signed(), 5);
+        assert_eq!(ModInt::<10>::new(6).to_signed(), -4);
+    }
+
+    #[test]
+    fn signed_representative_for_the_largest_modulus() {
+        type G = ModInt<18446744073709551557>;
+        let half = 18446744073709551557 / 2;
+        assert_eq!(G::new(half).to_signed(), half as i64);
+        assert_eq!(G::new(half + 1).to_signed(), -(half as i64));
+        assert_eq!(G::new(i64::MAX as u64).to_signed(), -9223372036854775750);
+        assert_eq!(G::new(1 << 63).to_signed(), -9223372036854775749);
+        assert_eq!(G::new(18446744073709551556).to_signed(), -1);
+    }
+
+    #[test]
+    fn multiplicative_orders() {
+        assert_eq!(F::new(5).multiplicative_order(), Some(862));
+        assert_eq!(F::new(1).multiplicative_order(), Some(1));
+        assert_eq!(F::new(862).multiplicative_order(), Some(2));
+        assert_eq!(F::new(4).multiplicative_order(), Some(431));
+        assert_eq!(F::new(0).multiplicative_order(), None);
+    }
+
+    #[test]
+    fn primitive_root_generates_the_group() {
+        let g = find_primitive_root::<863>();
+        assert_eq!(g, F::new(5));
+        assert!(g.is_primitive_root());
+        assert!(!F::new(4).is_primitive_root());
+        let mut powers: Vec<u64> = (0..862).map(|k| g.power(k).to_int()).collect();
+        powers.sort_unstable();
+        assert_eq!(powers, (1..863).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sampling_is_uniform() {
+        use rand::rngs::SmallRng;
+        use rand::SeedableRng;
+        let mut rng = SmallRng::seed_from_u64(0);
+        let n = 70_000;
+        let mut counts = [0u32; 7];
+        for _ in 0..n {
+            counts[rng.gen::<ModInt<7>>().to_int() as usize] += 1;
+        }
+        let expected = n as f64 / 7.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        // The 99.9% quantile of the chi-squared distribution with 6 degrees of freedom.
+        assert!(chi_squared < 22.46, "chi-squared {}", chi_squared);
+        assert!(counts[0] > 0);
+    }
+
+    #[test]
+    fn power_works_for_moduli_beyond_32_bits() {
+        // 2^61 - 1 is a Mersenne prime.
+        type G = ModInt<2305843009213693951>;
+        let x = G::new(1 << 40);
+        assert!(x.checked_mul(x).is_none());
+        assert_eq!(x.power(2305843009213693950), G::new(1));
+        assert_eq!(x.power(2), x * x);
+        assert_eq!(x.power(5), x * x * x * x * x);
+        assert_eq!(x.power(0), G::new(1));
+        assert_eq!(F::new(5).power(3), F::new(125));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn equal_elements_have_equal_hashes() {
+        use std::collections::hash_map::DefaultHasher;
+        fn hash(x: F) -> u64 {
+            let mut h = DefaultHasher::new();
+            x.hash(&mut h);
+            h.finish()
+        }
+        let x = F::new(5);
+        let same = [
+            F::new(5 + 863),
+            F::new_unchecked(5),
+            F::from_bytes(868u64.to_le_bytes()),
+            F::new(2) + F::new(3),
+            F::new(3) - F::new(861),
+            -F::new(858),
+            F::new(217) * F::new(4),
+            F::new(5).canonicalize(),
+            F::new(5).power(863),
+        ];
+        for &y in same.iter() {
+            assert_eq!(y, x);
+            assert_eq!(hash(y), hash(x));
+            assert_eq!(y.to_int(), 5);
+        }
+    }
+
+    #[test]
+    fn reduce_into_other_modulus() {
+        assert_eq!(F::new(5).reduce_into::<7>(), ModInt::<7>::new(5));
+        assert_eq!(F::new(862).reduce_into::<7>(), ModInt::<7>::new(862 % 7));
+        assert_eq!(ModInt::<7>::new(6).reduce_into::<863>(), F::new(6));
+    }
+
+    #[test]
+    fn new_unchecked_agrees_with_new_in_range() {
+        for n in [0, 1, 431, 862] {
+            assert_eq!(F::new_unchecked(n), F::new(n));
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn new_unchecked_rejects_unreduced_input_in_debug_builds() {
+        F::new_unchecked(863);
+    }
+
+    #[test]
+    fn addition_subtraction_and_negation_for_large_moduli() {
+        fn check<const MOD: u64>(values: &[u64]) {
+            for &a in values {
+                for &b in values {
+                    let sum = ((a as u128 + b as u128) % MOD as u128) as u64;
+                    let (x, y) = (ModInt::<MOD>::new(a), ModInt::<MOD>::new(b));
+                    assert_eq!((x + y).to_int(), sum);
+                    let mut z = x;
+                    z += y;
+                    assert_eq!(z.to_int(), sum);
+                    let expected = ((a as u128 + MOD as u128 - b as u128) % MOD as u128) as u64;
+                    assert_eq!((x - y).to_int(), expected);
+                    let mut z = x;
+                    z -= y;
+                    assert_eq!(z.to_int(), expected);
+                    assert_eq!((ModInt::<MOD>::new(0) - y).to_int(), (-y).to_int());
+                    assert_eq!(-y + y, ModInt::<MOD>::new(0));
+                }
+            }
+        }
+        // 2^63 - 25 is prime; 2^64 - 59 is the largest prime below 2^64.
+        check::<9223372036854775783>(&[0, 1, 2, 1 << 62, 9223372036854775782]);
+        check::<18446744073709551557>(&[0, 1, 1 << 63, 18446744073709551556]);
+        check::<863>(&[0, 1, 431, 862]);
+    }
+
+    #[test]
+    fn all_yields_every_element_once() {
+        let elements: Vec<F> = all::<863>().collect();
+        assert_eq!(elements.len(), 863);
+        let mut values: Vec<u64> = elements.iter().map(|x| x.to_int()).collect();
+        values.dedup();
+        assert_eq!(values, (0..863).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn macro_matches_constructor() {
+        assert_eq!(modint!(863; 5), F::new(5));
+        assert_eq!(modint!(863; 863 + 7), F::new(7));
+        let x: F = modint!(862);
+        assert_eq!(x, F::new(862));
+        const P: u64 = 11;
+        assert_eq!(modint!(P; 12), ModInt::<11>::new(1));
+    }
+
+    #[test]
+    fn default_is_zero() {
+        assert_eq!(F::default(), F::zero());
+        let mut x = F::new(5);
+        assert_eq!(core::mem::take(&mut x), F::new(5));
+        assert_eq!(x, F::zero());
+    }
+
+    #[test]
+    fn comparison_with_integers() {
+        assert!(F::new(0) == 863u64);
+        assert!(F::new(5) == 5u64);
+        assert!(F::new(5) == 868u64);
+        assert!(F::new(5) != 6u64);
+        assert!(F::new(u64::MAX) == u64::MAX);
+    }
+
+    #[test]
+    fn checked_mul_detects_overflow_of_the_naive_product() {
+        for x in all::<863>() {
+            assert_eq!(x.checked_mul(F::new(862)), Some(x * F::new(862)));
+        }
+        // 2^64 - 59 is a prime, for which the products of large representatives overflow.
+        type G = ModInt<18446744073709551557>;
+        let (x, y) = (G::new(1 << 32), G::new(u64::MAX - 100));
+        assert_eq!(x.checked_mul(x), None);
+        assert_eq!(y.checked_mul(G::new(2)), None);
+        assert_eq!(x.checked_mul(G::new(3)), Some(x * G::new(3)));
+        assert_eq!(
+            G::new((1 << 32) - 1).checked_mul(G::new(1 << 32)),
+            Some(G::new(u64::MAX - 0xffff_ffff))
+        );
+    }
+
+    #[test]
+    fn exponents_reduced_by_the_order_give_the_same_power() {
+        // The multiplicative order of every non-zero element divides 862, and 0^n = 0 for n > 0.
+        let order = 862;
+        for x in all::<863>() {
+            for &n in [1, 5, 431, 862, 863, 10_000, u64::MAX - order].iter() {
+                assert_eq!(x.power(n), x.power(n + order));
+                assert_eq!(x.power_with_order(n, order), x.power(n));
+            }
+            assert_eq!(x.power_with_order(0, order), F::identity());
+        }
+        assert_eq!(F::zero().power_with_order(order, order), F::zero());
+    }
+
+    #[test]
+    fn power_by_limbs_matches_power() {
+        for x in [F::zero(), F::new(1), F::new(5), F::new(862)].iter() {
+            for &n in [0, 1, 2, 431, 862, 1 << 40, u64::MAX].iter() {
+                assert_eq!(x.power_bits(&[n]), x.power(n));
+            }
+            assert_eq!(x.power_bits(&[]), F::identity());
+            assert_eq!(x.power_bits(&[7, 0, 0]), x.power(7));
+        }
+        // Computed independently: 5^(2^64 + 3) = 175 and 5^(7 * 2^64 + 12345) = 552 modulo 863.
+        assert_eq!(F::new(5).power_bits(&[3, 1]), F::new(175));
+        assert_eq!(F::new(5).power_bits(&[12345, 7]), F::new(552));
+        // 2^(2^127) = 2^(2^127 mod 61) = 2^6 modulo the Mersenne prime 2^61 - 1.
+        type G = ModInt<2305843009213693951>;
+        assert_eq!(G::new(2).power_bits(&[0, 1 << 63]), G::new(64));
+    }
+
+    #[test]
+    fn prime_modulus_rejects_composites() {
+        assert_eq!(PrimeModulus::<15>::new(), Err(Error::CompositeModulus));
+        assert_eq!(PrimeModulus::<1>::new(), Err(Error::CompositeModulus));
+        let field = PrimeModulus::<863>::new().unwrap();
+        for n in [0, 5, 862, 863, u64::MAX].iter().copied() {
+            assert_eq!(field.element(n), F::new(n));
+        }
+        assert_eq!(ModInt::<15>::try_new(2), Err(Error::CompositeModulus));
+        assert_eq!(F::try_new(868), Ok(F::new(5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "not prime")]
+    fn assert_prime_modulus_panics_for_composites() {
+        ModInt::<15>::assert_prime_modulus();
+    }
+}
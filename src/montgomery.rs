@@ -0,0 +1,154 @@
+use crate::complexification::{self, Complex};
+use crate::field::Field;
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::prime_field::PrimeField;
+use crate::rational_point::RationalPoint;
+
+/// The Montgomery form ```Bv^2 = u^3 + Au^2 + u``` of an elliptic curve ```y^2 = x^3 + ax + b```.
+///
+/// If ```α``` is a root of ```x^3 + ax + b``` and ```3α^2 + a = s^2``` with ```s != 0```,
+/// the curves are isomorphic by ```(x, y) -> ((x - α) / s, y / s)``` with ```A = 3α / s``` and ```B = 1 / s```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MontgomeryForm<T> {
+    a: T,
+    b: T,
+    alpha: T,
+    s: T,
+}
+
+impl<T: Copy> MontgomeryForm<T> {
+    /// The coefficients ```(A, B)``` of the Montgomery form.
+    pub fn coefficients(&self) -> (T, T) {
+        (self.a, self.b)
+    }
+}
+
+impl<T: PrimeField + Field> MontgomeryForm<Complex<T>> {
+    /// The Montgomery form of ```y^2 = x^3 + ax + b``` over ```F_(p^2)```,
+    /// or ```None``` if the curve does not admit it, i.e. no root ```α``` of ```x^3 + ax + b``` makes ```3α^2 + a``` a non-zero square.
+    ///
+    /// The roots are searched among all the ```p^2``` elements, so this takes ```O(p^2)``` steps.
+    pub fn from_weierstrass(a: Complex<T>, b: Complex<T>) -> Option<Self> {
+        let id = Complex::<T>::identity();
+        let three = id + id + id;
+        for alpha in complexification::all::<T>() {
+            if alpha * alpha * alpha + a * alpha + b != Complex::zero() {
+                continue;
+            }
+            let s = match (three * alpha * alpha + a).sqrt() {
+                Some(s) if s != Complex::zero() => s,
+                _ => continue,
+            };
+            let s_inv = s.inverse()?;
+            return Some(Self {
+                a: three * alpha * s_inv,
+                b: s_inv,
+                alpha,
+                s,
+            });
+        }
+        None
+    }
+}
+
+impl<T: Field> MontgomeryForm<T> {
+    /// Map a point on ```y^2 = x^3 + ax + b``` to the Montgomery form by ```(x, y) -> ((x - α) / s, y / s)```.
+    pub fn to_montgomery_point(&self, p: &RationalPoint<T>) -> RationalPoint<T> {
+        let s_inv = self.s.inverse().unwrap();
+        match *p {
+            RationalPoint::O => RationalPoint::O,
+            RationalPoint::Point(x, y) => RationalPoint::Point((x - self.alpha) * s_inv, y * s_inv),
+        }
+    }
+
+    /// The inverse of ```to_montgomery_point```, i.e. ```(u, v) -> (su + α, sv)```.
+    pub fn from_montgomery_point(&self, p: &RationalPoint<T>) -> RationalPoint<T> {
+        match *p {
+            RationalPoint::O => RationalPoint::O,
+            RationalPoint::Point(u, v) => RationalPoint::Point(self.s * u + self.alpha, self.s * v),
+        }
+    }
+
+    /// The Montgomery ladder on ```Bv^2 = u^3 + Au^2 + u```, which needs only the ```u```-coordinates.
+    ///
+    /// It returns the ```u```-coordinate of ```nP``` for a point ```P``` with the ```u```-coordinate ```u```,
+    /// or ```None``` if ```nP = O```.
+    /// Each step is a doubling and a differential addition in the projective coordinates ```(X : Z)```, so no inversion is needed until the end.
+    pub fn ladder(&self, u: T, n: u64) -> Option<T> {
+        let id = T::identity();
+        // The differential addition degenerates for the point (0, 0) of order 2.
+        if u == T::zero() {
+            return if n.is_multiple_of(2) { None } else { Some(u) };
+        }
+        let a24 = (self.a + id + id) * (id + id + id + id).inverse().unwrap();
+        let (mut x2, mut z2) = (id, T::zero());
+        let (mut x3, mut z3) = (u, id);
+        for i in (0..64 - n.leading_zeros()).rev() {
+            let bit = (n >> i) & 1 == 1;
+            if bit {
+                core::mem::swap(&mut x2, &mut x3);
+                core::mem::swap(&mut z2, &mut z3);
+            }
+            let sum = x2 + z2;
+            let sum_sq = sum * sum;
+            let diff = x2 - z2;
+            let diff_sq = diff * diff;
+            let e = sum_sq - diff_sq;
+            let da = (x3 - z3) * sum;
+            let cb = (x3 + z3) * diff;
+            x3 = (da + cb) * (da + cb);
+            z3 = u * (da - cb) * (da - cb);
+            x2 = sum_sq * diff_sq;
+            z2 = e * (diff_sq + a24 * e);
+            if bit {
+                core::mem::swap(&mut x2, &mut x3);
+                core::mem::swap(&mut z2, &mut z3);
+            }
+        }
+        Some(x2 * z2.inverse()?)
+    }
+}
+
+/// The coefficients ```(A, B)``` of the Montgomery form of ```y^2 = x^3 + ax + b``` over ```F_(p^2)```,
+/// or ```None``` if the curve does not admit it. See ```MontgomeryForm::from_weierstrass```.
+pub fn to_montgomery<T: PrimeField + Field>(
+    a: Complex<T>,
+    b: Complex<T>,
+) -> Option<(Complex<T>, Complex<T>)> {
+    MontgomeryForm::from_weierstrass(a, b).map(|m| m.coefficients())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_curves::{random_points, supersingular_curve, C};
+
+    /// The order of the point with the ```u```-coordinate ```u``` on the Montgomery form, found by the ladder.
+    fn ladder_order(m: &MontgomeryForm<C>, u: C) -> u64 {
+        (1..).find(|&k| m.ladder(u, k).is_none()).unwrap()
+    }
+
+    #[test]
+    fn transformation_preserves_orders() {
+        let (a, b) = supersingular_curve();
+        let m = MontgomeryForm::from_weierstrass(a, b).unwrap();
+        assert_eq!(to_montgomery(a, b), Some(m.coefficients()));
+        let (big_a, big_b) = m.coefficients();
+        for p in random_points(0).take(50) {
+            let q = m.to_montgomery_point(&p);
+            assert_eq!(m.from_montgomery_point(&q), p);
+            let (u, v) = match q.affine() {
+                Some(uv) => uv,
+                None => continue,
+            };
+            assert_eq!(big_b * v * v, u * u * u + big_a * u * u + u);
+            let order = p.order_naive(a, 44).unwrap();
+            assert_eq!(ladder_order(&m, u), order);
+            for k in 1..order {
+                let kq = m.to_montgomery_point(&p.multiply_rational_point(a, k));
+                assert_eq!(m.ladder(u, k), kq.affine().map(|(u, _)| u));
+            }
+        }
+    }
+}
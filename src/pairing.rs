@@ -0,0 +1,136 @@
+use crate::field::Field;
+use crate::rational_point::RationalPoint;
+
+/// The line through ```t``` and ```r``` (the tangent if ```t = r```) on ```y^2 = x^3 + ax + b```
+/// divided by the vertical line through ```t + r```, evaluated at ```q```.
+///
+/// It returns the numerator, the denominator and ```t + r```.
+fn line<T: Field>(
+    t: &RationalPoint<T>,
+    r: &RationalPoint<T>,
+    q: (T, T),
+    a: T,
+) -> (T, T, RationalPoint<T>) {
+    let (xq, yq) = q;
+    let sum = t.add_rational_points(r, a);
+    match (*t, *r) {
+        (RationalPoint::O, _) | (_, RationalPoint::O) => (T::identity(), T::identity(), sum),
+        (RationalPoint::Point(x1, y1), RationalPoint::Point(x2, y2)) => {
+            if x1 == x2 && y1 == -y2 {
+                // The vertical line through t and r = -t, and t + r = O.
+                (xq - x1, T::identity(), sum)
+            } else {
+                let id = T::identity();
+                let lambda = if x1 == x2 {
+                    ((id + id + id) * x1 * x1 + a) * ((id + id) * y1).inverse().unwrap()
+                } else {
+                    (y2 - y1) * (x2 - x1).inverse().unwrap()
+                };
+                let x3 = match sum {
+                    RationalPoint::Point(x3, _) => x3,
+                    RationalPoint::O => unreachable!(),
+                };
+                (yq - y1 - lambda * (xq - x1), xq - x3, sum)
+            }
+        }
+    }
+}
+
+/// Miller's algorithm, i.e. ```f(q)``` for the function ```f``` with the divisor ```n(P) - n(O)```,
+/// where ```nP = O```. It returns ```None``` if a line evaluated on the way vanishes at ```q```.
+fn miller<T: Field>(p: &RationalPoint<T>, q: (T, T), n: u64, a: T) -> Option<T> {
+    let mut num = T::identity();
+    let mut den = T::identity();
+    let mut t = *p;
+    for i in (0..63 - n.leading_zeros()).rev() {
+        let (l, v, doubled) = line(&t, &t, q, a);
+        num = num * num * l;
+        den = den * den * v;
+        t = doubled;
+        if (n >> i) & 1 == 1 {
+            let (l, v, added) = line(&t, p, q, a);
+            num = num * l;
+            den = den * v;
+            t = added;
+        }
+    }
+    if num == T::zero() || den == T::zero() {
+        None
+    } else {
+        Some(num * den.inverse()?)
+    }
+}
+
+/// The Weil pairing ```e_n(P, Q)``` of points ```P, Q``` with ```nP = nQ = O``` on ```y^2 = x^3 + ax + b```,
+/// computed as ```(-1)^n f_P(Q) / f_Q(P)``` by Miller's algorithm.
+///
+/// Over ```F_(p^2)``` it takes values in the ```n```-th roots of unity of ```F_(p^2)```,
+/// and it is bilinear, i.e. ```e_n(aP, bQ) = e_n(P, Q)^(ab)```.
+/// It returns ```None``` if ```n = 0```, if ```nP = O``` or ```nQ = O``` fails,
+/// or if the evaluation degenerates, which may happen when ```Q``` is a multiple of ```P```.
+pub fn weil_pairing<T: Field>(
+    p: &RationalPoint<T>,
+    q: &RationalPoint<T>,
+    order: u64,
+    a: T,
+) -> Option<T> {
+    if order == 0
+        || p.multiply_rational_point(a, order) != RationalPoint::O
+        || q.multiply_rational_point(a, order) != RationalPoint::O
+    {
+        return None;
+    }
+    let (xp, yp, xq, yq) = match (*p, *q) {
+        (RationalPoint::Point(xp, yp), RationalPoint::Point(xq, yq)) => (xp, yp, xq, yq),
+        _ => return Some(T::identity()),
+    };
+    if p == q {
+        return Some(T::identity());
+    }
+    let e = miller(p, (xq, yq), order, a)? * miller(q, (xp, yp), order, a)?.inverse()?;
+    if order % 2 == 1 {
+        Some(-e)
+    } else {
+        Some(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identities::Identity;
+    use crate::test_curves::{supersingular_curve, torsion_points, C};
+
+    /// Two points generating the 11-torsion of ```supersingular_curve```.
+    fn torsion_basis() -> (C, RationalPoint<C>, RationalPoint<C>) {
+        let (a, _) = supersingular_curve();
+        let mut points = torsion_points(0);
+        let p = points.next().unwrap();
+        let q = points
+            .find(|q| matches!(weil_pairing(&p, q, 11, a), Some(e) if e != C::identity()))
+            .unwrap();
+        (a, p, q)
+    }
+
+    #[test]
+    fn weil_pairing_is_bilinear() {
+        let (a, p, q) = torsion_basis();
+        let e = weil_pairing(&p, &q, 11, a).unwrap();
+        assert_eq!(e.modpow(11), C::identity());
+        let mut checked = 0;
+        for s in 1..11 {
+            for t in 1..11 {
+                let sp = p.multiply_rational_point(a, s);
+                let tq = q.multiply_rational_point(a, t);
+                if let Some(f) = weil_pairing(&sp, &tq, 11, a) {
+                    assert_eq!(f, e.modpow(s * t));
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 50);
+        assert_eq!(weil_pairing(&p, &p, 11, a), Some(C::identity()));
+        assert_eq!(weil_pairing(&p, &q, 0, a), None);
+        assert_eq!(weil_pairing(&p, &q, 5, a), None);
+    }
+}
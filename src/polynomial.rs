@@ -1,6 +1,17 @@
+use crate::characteristic::Characteristic;
 use crate::identities::{Identity, Zero};
-use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::inverse::Inverse;
+use crate::prime_field::PrimeField;
+use crate::signed::Signed;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
 
 /// Polynomials (in 1 variable).
 ///
@@ -17,6 +28,11 @@ impl<T> Polynomial<T> {
         self.degree
     }
 
+    /// The coefficients starting with 0-th coefficient.
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
     /// Make constant from an element in the coefficient ring.
     pub fn new_constant(t: T) -> Self {
         Self {
@@ -42,9 +58,9 @@ impl<T: Zero + Eq + Copy> Polynomial<T> {
     ///
     /// If the coefficient of highest degree is equal to zero, remove it recursively.
     /// To realize this, ```Zero``` must be implemented for ```T```.
-    pub fn new(v: &Vec<T>) -> Self {
+    pub fn new(v: &[T]) -> Self {
         let mut f: Vec<T> = Vec::new();
-        if v.len() == 0 {
+        if v.is_empty() {
             f.push(T::zero());
             Self {
                 coefficients: f,
@@ -53,7 +69,7 @@ impl<T: Zero + Eq + Copy> Polynomial<T> {
         } else {
             f.push(v[0]);
             for &t in &v[1..] {
-                f.push(t.clone());
+                f.push(t);
             }
             while f.len() > 1 {
                 if let Some(&t) = f.last() {
@@ -71,99 +87,586 @@ impl<T: Zero + Eq + Copy> Polynomial<T> {
             }
         }
     }
+
+    /// Drop the terms of degree greater than ```max_degree```, i.e. ```f mod x^(max_degree + 1)```.
+    /// The zeros of highest degree are removed as in ```new```.
+    pub fn truncate(&self, max_degree: usize) -> Self {
+        let n = self.coefficients.len().min(max_degree + 1);
+        Self::new(&self.coefficients[..n])
+    }
+
+    /// The same polynomial with the zeros of highest degree removed as in ```new```.
+    pub fn normalized(&self) -> Self {
+        Self::new(&self.coefficients)
+    }
+
+    /// Multiply by ```x^k```, i.e. prepend ```k``` zero coefficients.
+    pub fn shift(&self, k: usize) -> Self {
+        let mut v: Vec<T> = vec![T::zero(); k];
+        v.extend_from_slice(&self.coefficients);
+        Self::new(&v)
+    }
+
+    /// Divide by ```x^k```, i.e. remove the ```k``` coefficients of the lowest degrees,
+    /// or ```None``` unless all of them are zero. The zero polynomial is divisible by every ```x^k```.
+    pub fn divide_by_x_pow(&self, k: usize) -> Option<Self> {
+        if self.strict_deg().is_none() {
+            return Some(self.clone());
+        }
+        if k > self.degree || self.coefficients[..k].iter().any(|&c| c != T::zero()) {
+            return None;
+        }
+        Some(Self::new(&self.coefficients[k..]))
+    }
 }
 
 impl<T: Zero + Identity + Mul<Output = T> + MulAssign + AddAssign + Copy + Eq> Polynomial<T> {
-    /// The evaluate function.
+    /// The evaluate function, same as ```f.eval(t)```.
     pub fn evaluate(f: &Self, t: T) -> T {
-        let mut t_pow = T::identity();
+        f.eval(t)
+    }
+
+    /// Evaluate the polynomial at ```t``` by Horner's method,
+    /// i.e. ```f(t) = (...(c_n t + c_(n-1)) t + ...) t + c_0```, which needs only ```n``` multiplications.
+    pub fn eval(&self, t: T) -> T {
+        Self::evaluate_iter(self.coefficients.iter().rev().copied(), t)
+    }
+
+    /// Evaluate a polynomial at ```t``` by Horner's method without building a ```Polynomial```.
+    ///
+    /// The coefficients are read from the highest degree, i.e. ```c_n, c_(n-1), ..., c_0```,
+    /// so that huge or lazily generated polynomials are evaluated in constant memory.
+    pub fn evaluate_iter<I: IntoIterator<Item = T>>(coeffs: I, t: T) -> T {
         let mut ans = T::zero();
-        for &c in &f.coefficients {
-            ans += c * t_pow;
-            t_pow *= t;
+        for c in coeffs {
+            ans *= t;
+            ans += c;
         }
         ans
     }
 }
 
-/// Implementation of ```Display```.
-impl<T: fmt::Display + Zero + Identity + Eq> fmt::Display for Polynomial<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut s: String = String::new();
-        let mut flag = false;
-        if self.degree == 0 || self.coefficients[0] != T::zero() {
-            s.push_str(&self.coefficients[0].to_string())
-        } else {
-            flag = true;
+impl<T: Copy> Polynomial<T> {
+    /// Evaluate the polynomial at ```t``` in a ring containing the coefficients by Horner's method,
+    /// e.g. a polynomial over ```F_p``` at an element of ```F_(p^2)```,
+    /// where the coefficients are embedded by ```From```.
+    pub fn evaluate_in<U: From<T> + Zero + Add<Output = U> + Mul<Output = U> + Copy>(
+        &self,
+        t: U,
+    ) -> U {
+        let mut ans = U::zero();
+        for &c in self.coefficients.iter().rev() {
+            ans = ans * t + U::from(c);
         }
-        if self.degree > 0 && self.coefficients[1] != T::zero() {
-            if !flag {
-                s.push_str(&" + ");
-            } else {
-                flag = false;
+        ans
+    }
+}
+
+impl<T: Zero + Identity + Add<Output = T> + Mul<Output = T> + Copy + Eq> Polynomial<T> {
+    /// The formal derivative.
+    pub fn derivative(&self) -> Self {
+        let mut v: Vec<T> = Vec::new();
+        let mut k = T::zero();
+        for &c in &self.coefficients[1..] {
+            k = k + T::identity();
+            v.push(k * c);
+        }
+        Polynomial::new(&v)
+    }
+
+    /// The pair ```(f(t), f'(t))``` in a single pass of Horner's method,
+    /// i.e. ```f' = f' t + f``` and ```f = f t + c``` for each coefficient ```c``` from the highest degree,
+    /// without building ```derivative()```.
+    pub fn eval_with_derivative(&self, t: T) -> (T, T) {
+        let mut value = T::zero();
+        let mut slope = T::zero();
+        for &c in self.coefficients.iter().rev() {
+            slope = slope * t + value;
+            value = value * t + c;
+        }
+        (value, slope)
+    }
+}
+
+/// The number of coefficients below which ```karatsuba_mul``` falls back to the schoolbook method.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+impl<T: Zero + Add<Output = T> + AddAssign + Sub<Output = T> + Mul<Output = T> + Copy + Eq>
+    Polynomial<T>
+{
+    /// The product by Karatsuba's method, which is the same as ```*``` but needs only ```O(n^1.59)``` multiplications.
+    ///
+    /// Writing ```f = f_0 + x^m f_1``` and ```g = g_0 + x^m g_1```, the product is made from the three products
+    /// ```f_0 g_0```, ```f_1 g_1``` and ```(f_0 + f_1)(g_0 + g_1)``` computed recursively.
+    /// Below ```KARATSUBA_THRESHOLD``` coefficients the schoolbook method is faster and used instead.
+    pub fn karatsuba_mul(&self, rhs: &Self) -> Self {
+        Self::new(&karatsuba(&self.coefficients, &rhs.coefficients))
+    }
+}
+
+/// The coefficients of the product of the polynomials with the coefficients ```f``` and ```g```, see ```karatsuba_mul```.
+fn karatsuba<T: Zero + Add<Output = T> + AddAssign + Sub<Output = T> + Mul<Output = T> + Copy>(
+    f: &[T],
+    g: &[T],
+) -> Vec<T> {
+    let mut v: Vec<T> = vec![T::zero(); f.len() + g.len() - 1];
+    if f.len().min(g.len()) < KARATSUBA_THRESHOLD {
+        for (i, &c) in f.iter().enumerate() {
+            for (j, &d) in g.iter().enumerate() {
+                v[i + j] += c * d;
             }
-            if self.coefficients[1] != T::identity() {
-                s.push_str(&self.coefficients[1].to_string());
+        }
+        return v;
+    }
+    // Both of the halves are non-empty since m < f.len(), g.len().
+    let m = f.len().min(g.len()) / 2;
+    let (f0, f1) = f.split_at(m);
+    let (g0, g1) = g.split_at(m);
+    let z0 = karatsuba(f0, g0);
+    let z2 = karatsuba(f1, g1);
+    let f01 = add_slices(f0, f1);
+    let g01 = add_slices(g0, g1);
+    let z1 = karatsuba(&f01, &g01);
+    for (i, &c) in z1.iter().enumerate() {
+        v[i + m] += c;
+    }
+    for (i, &c) in z0.iter().enumerate() {
+        v[i] += c;
+        v[i + m] = v[i + m] - c;
+    }
+    for (i, &c) in z2.iter().enumerate() {
+        v[i + 2 * m] += c;
+        v[i + m] = v[i + m] - c;
+    }
+    v
+}
+
+/// The coefficient-wise sum of slices of possibly different lengths.
+fn add_slices<T: Zero + Add<Output = T> + Copy>(f: &[T], g: &[T]) -> Vec<T> {
+    (0..f.len().max(g.len()))
+        .map(|i| {
+            let c = f.get(i).copied().unwrap_or_else(T::zero);
+            let d = g.get(i).copied().unwrap_or_else(T::zero);
+            c + d
+        })
+        .collect()
+}
+
+impl<T: Zero + Identity + Inverse + Add<Output = T> + Mul<Output = T> + Copy + Eq> Polynomial<T> {
+    /// The formal antiderivative with zero constant term, i.e. ```c_i x^i``` is mapped to ```c_i / (i + 1) x^(i + 1)```.
+    ///
+    /// It returns ```None``` if some ```i + 1``` is not invertible, i.e. divisible by the characteristic.
+    pub fn integrate(&self) -> Option<Self> {
+        let mut v: Vec<T> = vec![T::zero()];
+        let mut k = T::zero();
+        for &c in &self.coefficients {
+            k = k + T::identity();
+            v.push(c * k.inverse()?);
+        }
+        Some(Polynomial::new(&v))
+    }
+}
+
+impl<
+        T: Zero
+            + Identity
+            + Inverse
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+    > Polynomial<T>
+{
+    /// The coefficient of highest degree.
+    pub fn leading_coefficient(&self) -> T {
+        self.coefficients[self.degree]
+    }
+
+    /// Make it monic by dividing by the leading coefficient. The zero polynomial is returned as it is.
+    pub fn monic(&self) -> Self {
+        if self.strict_deg().is_none() {
+            return self.clone();
+        }
+        let inv = self.leading_coefficient().inverse().unwrap();
+        let v: Vec<T> = self.coefficients.iter().map(|&c| c * inv).collect();
+        Polynomial::new(&v)
+    }
+
+    /// The content, i.e. the unit ```c``` with ```self = c * self.primitive_part()```.
+    ///
+    /// Over the integers the content is the gcd of the coefficients, but over a field every non-zero coefficient is a unit,
+    /// so the leading coefficient is taken here to normalize the polynomial. It is ```0``` for the zero polynomial.
+    pub fn content(&self) -> T {
+        self.leading_coefficient()
+    }
+
+    /// The primitive part ```self / self.content()```, which is monic over a field.
+    /// The zero polynomial is returned as it is.
+    pub fn primitive_part(&self) -> Self {
+        self.monic()
+    }
+
+    /// Division with remainder. It returns ```Some((q, r))``` with ```self = q * rhs + r``` and ```deg r < deg rhs```,
+    /// or ```None``` if the leading coefficient of ```rhs``` is not invertible (e.g. ```rhs = 0```).
+    pub fn div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        let inv = rhs.leading_coefficient().inverse()?;
+        if self.degree < rhs.degree {
+            return Some((Polynomial::zero(), self.clone()));
+        }
+        let mut q: Vec<T> = vec![T::zero(); self.degree - rhs.degree + 1];
+        let mut r: Vec<T> = self.coefficients.clone();
+        for i in (0..q.len()).rev() {
+            let c = r[i + rhs.degree] * inv;
+            q[i] = c;
+            for (j, &d) in rhs.coefficients.iter().enumerate() {
+                r[i + j] = r[i + j] - c * d;
+            }
+        }
+        Some((Polynomial::new(&q), Polynomial::new(&r)))
+    }
+
+    /// The monic greatest common divisor computed by the Euclidean algorithm. Set gcd(0, 0) = 0.
+    pub fn gcd(&self, rhs: &Self) -> Self {
+        let mut f = self.clone();
+        let mut g = rhs.clone();
+        while g.strict_deg().is_some() {
+            let (_, r) = f.div_rem(&g).unwrap();
+            f = g;
+            g = r;
+        }
+        f.monic()
+    }
+
+    /// The extended Euclidean algorithm. It returns ```(g, s, t)``` with ```s * self + t * rhs = g```,
+    /// where ```g``` is the monic greatest common divisor, i.e. ```g = self.gcd(rhs)```.
+    pub fn gcd_extended(&self, rhs: &Self) -> (Self, Self, Self) {
+        let (mut f, mut g) = (self.clone(), rhs.clone());
+        let (mut s0, mut s1) = (Polynomial::identity(), Polynomial::zero());
+        let (mut t0, mut t1) = (Polynomial::zero(), Polynomial::identity());
+        while g.strict_deg().is_some() {
+            let (q, r) = f.div_rem(&g).unwrap();
+            let s = s0 - q.clone() * s1.clone();
+            let t = t0 - q * t1.clone();
+            f = g;
+            g = r;
+            s0 = s1;
+            s1 = s;
+            t0 = t1;
+            t1 = t;
+        }
+        if f.strict_deg().is_none() {
+            return (f, s0, t0);
+        }
+        let c = Polynomial::new_constant(f.leading_coefficient().inverse().unwrap());
+        (f.monic(), c.clone() * s0, c * t0)
+    }
+
+    /// The resultant ```Res(self, rhs)``` computed along the Euclidean remainder sequence.
+    ///
+    /// With ```m = deg f```, ```n = deg g``` and ```r = f mod g``` of degree ```k```,
+    /// it uses ```Res(f, g) = (-1)^(mn) lc(g)^(m - k) Res(g, r)``` and ```Res(f, c) = c^m``` for a constant ```c```.
+    /// It is ```0``` iff ```f``` and ```g``` have a common root (over an algebraic closure) or one of them is ```0```.
+    pub fn resultant(&self, rhs: &Self) -> T {
+        if self.strict_deg().is_none() || rhs.strict_deg().is_none() {
+            return T::zero();
+        }
+        let mut f = self.clone();
+        let mut g = rhs.clone();
+        let mut res = T::identity();
+        while g.degree > 0 {
+            let (_, r) = f.div_rem(&g).unwrap();
+            if r.strict_deg().is_none() {
+                return T::zero();
+            }
+            if f.degree % 2 == 1 && g.degree % 2 == 1 {
+                res = T::zero() - res;
+            }
+            for _ in r.degree..f.degree {
+                res = res * g.leading_coefficient();
+            }
+            f = g;
+            g = r;
+        }
+        for _ in 0..f.degree {
+            res = res * g.leading_coefficient();
+        }
+        res
+    }
+
+    /// The remainder of ```self * rhs``` divided by ```f```. ```f``` must not be ```0```.
+    fn mul_mod(&self, rhs: &Self, f: &Self) -> Self {
+        (self.clone() * rhs.clone()).div_rem(f).unwrap().1
+    }
+
+    /// The remainder of ```self^n``` divided by ```f``` computed by repeated squaring. ```f``` must not be ```0```.
+    pub fn modpow(&self, n: u64, f: &Self) -> Self {
+        let mut res = Polynomial::identity().div_rem(f).unwrap().1;
+        let mut a = self.div_rem(f).unwrap().1;
+        let mut m = n;
+        while m > 0 {
+            if m % 2 == 1 {
+                res = res.mul_mod(&a, f);
             }
-            s.push_str(&"x");
+            a = a.mul_mod(&a, f);
+            m /= 2;
         }
-        if self.degree > 1 {
-            for i in 2..=self.degree {
-                if self.coefficients[i] == T::zero() {
-                    continue;
+        res
+    }
+}
+
+impl<
+        T: Zero
+            + Identity
+            + Inverse
+            + Characteristic
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+    > Polynomial<T>
+{
+    /// The ```p```-th root of a polynomial of the form ```g(x^p)```, where ```p``` is the characteristic.
+    ///
+    /// Over ```F_(p^n)``` the Frobenius ```c -> c^p``` satisfies ```c^(p^n) = c```,
+    /// so the ```p```-th root of a coefficient ```c``` is the last element before ```c``` when the Frobenius is iterated from ```c```.
+    /// Over ```F_p``` it is ```c``` itself.
+    fn pth_root(&self) -> Self {
+        let p = T::characteristic();
+        let frobenius = |c: T| {
+            let mut res = T::identity();
+            let mut a = c;
+            let mut m = p;
+            while m > 0 {
+                if m % 2 == 1 {
+                    res = res * a;
                 }
-                if !flag {
-                    s.push_str(&" + ");
-                } else {
-                    flag = false;
+                a = a * a;
+                m /= 2;
+            }
+            res
+        };
+        let root = |c: T| {
+            let mut r = c;
+            loop {
+                let next = frobenius(r);
+                if next == c {
+                    return r;
                 }
-                if self.coefficients[i] != T::identity() {
-                    s.push_str(&self.coefficients[i].to_string());
+                r = next;
+            }
+        };
+        let v: Vec<T> = self
+            .coefficients
+            .iter()
+            .step_by(p as usize)
+            .map(|&c| root(c))
+            .collect();
+        Polynomial::new(&v)
+    }
+
+    /// Squarefree decomposition over a finite field ```F_q```.
+    ///
+    /// It returns pairs ```(g, m)``` of monic squarefree polynomials and multiplicities
+    /// such that ```self``` is the product of ```g^m``` up to a constant.
+    /// Factors which are ```p```-th powers (so that ```f' = 0``` on them) are handled by taking ```p```-th roots.
+    pub fn squarefree_decomposition(&self) -> Vec<(Self, usize)> {
+        let mut factors: Vec<(Self, usize)> = Vec::new();
+        if self.degree == 0 {
+            return factors;
+        }
+        let one = Polynomial::identity();
+        let mut c = self.gcd(&self.derivative());
+        let mut w = self.div_rem(&c).unwrap().0.monic();
+        let mut i = 1;
+        while w != one {
+            let y = w.gcd(&c);
+            let z = w.div_rem(&y).unwrap().0;
+            if z != one {
+                factors.push((z, i));
+            }
+            c = c.div_rem(&y).unwrap().0;
+            w = y;
+            i += 1;
+        }
+        if c != one {
+            let p = T::characteristic() as usize;
+            for (g, m) in c.pth_root().squarefree_decomposition() {
+                factors.push((g, m * p));
+            }
+        }
+        factors
+    }
+
+    /// The squarefree part, i.e. the product of the distinct monic irreducible factors.
+    ///
+    /// A polynomial has a repeated root iff its squarefree part has a smaller degree.
+    pub fn squarefree_part(&self) -> Self {
+        if self.strict_deg().is_none() {
+            return self.clone();
+        }
+        let mut f = Polynomial::identity();
+        for (g, _) in self.squarefree_decomposition() {
+            f *= g;
+        }
+        f
+    }
+}
+
+impl<
+        T: PrimeField
+            + Zero
+            + Identity
+            + Inverse
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+    > Polynomial<T>
+{
+    /// The factorization over ```F_p``` by the Cantor–Zassenhaus algorithm.
+    ///
+    /// It returns pairs ```(g, m)``` of monic irreducible polynomials and multiplicities
+    /// such that ```self``` is the product of ```g^m``` up to a constant, in ascending order of degree.
+    /// Each squarefree factor (see ```squarefree_decomposition```) is split by the distinct-degree factorization,
+    /// and then by the equal-degree factorization with random polynomials.
+    /// The random polynomials are drawn from a fixed seed, so that the result is deterministic.
+    pub fn factor(&self) -> Vec<(Self, usize)> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut factors: Vec<(Self, usize)> = Vec::new();
+        for (g, m) in self.squarefree_decomposition() {
+            for (h, d) in g.distinct_degree_factorization() {
+                for k in h.equal_degree_factorization(d, &mut rng) {
+                    factors.push((k, m));
                 }
-                s.push_str(&"x^");
-                s.push_str(&i.to_string());
             }
         }
-        write!(f, "{}", s)
+        factors.sort_by_key(|(g, _)| g.degree);
+        factors
+    }
+
+    /// The distinct-degree factorization of a monic squarefree polynomial.
+    ///
+    /// It returns pairs ```(h, d)``` where ```h``` is the product of all the irreducible factors of degree ```d```,
+    /// given by ```gcd(x^(p^d) - x, f)``` after removing the factors of smaller degrees.
+    fn distinct_degree_factorization(&self) -> Vec<(Self, usize)> {
+        let p = T::characteristic();
+        let x = Polynomial::new(&[T::zero(), T::identity()]);
+        let mut factors: Vec<(Self, usize)> = Vec::new();
+        let mut f = self.clone();
+        let mut h = x.clone();
+        let mut d = 1;
+        while f.degree >= 2 * d {
+            h = h.modpow(p, &f);
+            let g = (h.clone() - x.clone()).gcd(&f);
+            if g.degree > 0 {
+                f = f.div_rem(&g).unwrap().0;
+                h = h.div_rem(&f).unwrap().1;
+                factors.push((g, d));
+            }
+            d += 1;
+        }
+        if f.degree > 0 {
+            let d = f.degree;
+            factors.push((f, d));
+        }
+        factors
+    }
+
+    /// The equal-degree factorization of a monic squarefree polynomial whose irreducible factors all have degree ```d```.
+    ///
+    /// For a random ```a```, ```gcd(a^((p^d - 1) / 2) - 1, f)``` (or ```gcd(a + a^2 + ... + a^(2^(d - 1)), f)``` for ```p = 2```)
+    /// is a proper factor with probability about ```1/2```, and the factors found are split recursively.
+    fn equal_degree_factorization<R: Rng>(&self, d: usize, rng: &mut R) -> Vec<Self> {
+        if self.degree <= d {
+            return vec![self.clone()];
+        }
+        let p = T::characteristic();
+        loop {
+            let v: Vec<T> = (0..self.degree)
+                .map(|_| T::from_int(rng.gen_range(0, p)))
+                .collect();
+            let a = Polynomial::new(&v);
+            if a.degree == 0 {
+                continue;
+            }
+            // c = a^(p^i), and s is either a^(1 + p + ... + p^(d - 1)) or the trace a + a^2 + ... + a^(2^(d - 1)).
+            let mut c = a.clone();
+            let mut s = a.clone();
+            for _ in 1..d {
+                c = c.modpow(p, self);
+                s = if p == 2 {
+                    s + c.clone()
+                } else {
+                    s.mul_mod(&c, self)
+                };
+            }
+            let b = if p == 2 {
+                s
+            } else {
+                s.modpow((p - 1) / 2, self) - Polynomial::identity()
+            };
+            let g = b.gcd(self);
+            if g.degree > 0 && g.degree < self.degree {
+                let mut factors = g.equal_degree_factorization(d, rng);
+                factors.extend(
+                    self.div_rem(&g)
+                        .unwrap()
+                        .0
+                        .equal_degree_factorization(d, rng),
+                );
+                return factors;
+            }
+        }
+    }
+}
+
+/// Implementation of ```Display```.
+impl<T: fmt::Display + Zero + Identity + Eq> fmt::Display for Polynomial<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.print_f_of_x('x'))
     }
 }
 
 impl<T: fmt::Display + Zero + Identity + Eq> Polynomial<T> {
     /// Display as a function of ```x```.
+    ///
+    /// The terms are arranged in ascending order of degree.
     pub fn print_f_of_x(&self, x: char) -> String {
+        self.print_terms(x, |_| None)
+    }
+
+    /// The terms joined by ```+```, or by ```-``` where ```negate``` gives the negation of a coefficient to display instead.
+    fn print_terms(&self, x: char, negate: impl Fn(&T) -> Option<T>) -> String {
         let mut s: String = String::new();
-        let mut flag = false;
-        if self.degree == 0 || self.coefficients[0] != T::zero() {
-            s.push_str(&self.coefficients[0].to_string())
-        } else {
-            flag = true;
-        }
-        if self.degree > 0 && self.coefficients[1] != T::zero() {
-            if !flag {
-                s.push_str(&" + ");
+        for (i, c) in self.coefficients.iter().enumerate() {
+            if *c == T::zero() && self.degree > 0 {
+                continue;
+            }
+            let negated = negate(c);
+            let c = negated.as_ref().unwrap_or(c);
+            if s.is_empty() {
+                if negated.is_some() {
+                    s.push('-');
+                }
+            } else if negated.is_some() {
+                s.push_str(" - ");
             } else {
-                flag = false;
+                s.push_str(" + ");
             }
-            if self.coefficients[1] != T::identity() {
-                s.push_str(&self.coefficients[1].to_string());
+            if i == 0 || *c != T::identity() {
+                s.push_str(&c.to_string());
             }
-            s.push_str(&x.to_string());
-        }
-        if self.degree > 1 {
-            for i in 2..=self.degree {
-                if self.coefficients[i] == T::zero() {
-                    continue;
-                }
-                if !flag {
-                    s.push_str(&" + ");
-                } else {
-                    flag = false;
-                }
-                if self.coefficients[i] != T::identity() {
-                    s.push_str(&self.coefficients[i].to_string());
-                }
-                s.push_str(&x.to_string());
-                s.push_str(&"^");
+            if i > 0 {
+                s.push(x);
+            }
+            if i > 1 {
+                s.push('^');
                 s.push_str(&i.to_string());
             }
         }
@@ -171,6 +674,19 @@ impl<T: fmt::Display + Zero + Identity + Eq> Polynomial<T> {
     }
 }
 
+impl<T: fmt::Display + Zero + Identity + Eq + Copy + Neg<Output = T> + Signed> Polynomial<T> {
+    /// Display as a function of ```x``` as ```print_f_of_x``` does,
+    /// but a coefficient ```c``` with ```c.is_negative()``` is displayed as ```- (-c)```, e.g. ```-1 + x^2``` instead of ```862 + x^2``` over ```F_863```.
+    pub fn print_signed_f_of_x(&self, x: char) -> String {
+        self.print_terms(x, |&c| if c.is_negative() { Some(-c) } else { None })
+    }
+
+    /// A string using the sign of the coefficients instead of ```Display```, i.e. ```print_signed_f_of_x('x')```.
+    pub fn to_signed_string(&self) -> String {
+        self.print_signed_f_of_x('x')
+    }
+}
+
 /// Implementation of ```Add```.
 impl<T: Copy + Add<Output = T> + Zero + Eq> Add for Polynomial<T> {
     type Output = Self;
@@ -205,6 +721,14 @@ impl<T: Copy + Add<Output = T> + Zero + Eq> Add for Polynomial<T> {
 impl<T: Copy + Add<Output = T> + Zero + Eq> AddAssign for Polynomial<T> {
     /// Overloading the operator ```+=```.
     fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
+    }
+}
+
+/// Implementation of ```AddAssign``` by reference.
+impl<T: Copy + Add<Output = T> + Zero + Eq> AddAssign<&Polynomial<T>> for Polynomial<T> {
+    /// Overloading the operator ```+=```.
+    fn add_assign(&mut self, rhs: &Self) {
         let mut v: Vec<T> = Vec::new();
         v.push(self.coefficients[0] + rhs.coefficients[0]);
         if self.degree < rhs.degree {
@@ -264,6 +788,14 @@ impl<T: Copy + Sub<Output = T> + Zero + Eq> Sub for Polynomial<T> {
 impl<T: Copy + Sub<Output = T> + Zero + Eq> SubAssign for Polynomial<T> {
     /// Overloading the operator ```-=```.
     fn sub_assign(&mut self, rhs: Self) {
+        *self -= &rhs;
+    }
+}
+
+/// Implementation of ```SubAssign``` by reference.
+impl<T: Copy + Sub<Output = T> + Zero + Eq> SubAssign<&Polynomial<T>> for Polynomial<T> {
+    /// Overloading the operator ```-=```.
+    fn sub_assign(&mut self, rhs: &Self) {
         let mut v: Vec<T> = Vec::new();
         v.push(self.coefficients[0] - rhs.coefficients[0]);
         if self.degree < rhs.degree {
@@ -294,12 +826,10 @@ impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Eq> Mul
     type Output = Self;
     /// Overloading the operator ```*```.
     fn mul(self, rhs: Self) -> Self {
-        let mut v: Vec<T> = vec![T::zero(); self.degree * rhs.degree + 1];
-        for i in 0..=(self.degree * rhs.degree) {
-            for j in 0..=i {
-                if i - j <= rhs.degree && j <= self.degree {
-                    v[i] += self.coefficients[j] * rhs.coefficients[i - j];
-                }
+        let mut v: Vec<T> = vec![T::zero(); self.degree + rhs.degree + 1];
+        for (i, &c) in self.coefficients.iter().enumerate() {
+            for (j, &d) in rhs.coefficients.iter().enumerate() {
+                v[i + j] += c * d;
             }
         }
         Polynomial::new(&v)
@@ -312,18 +842,110 @@ impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Eq> Mul
 {
     /// Overloading the operator ```*=```.
     fn mul_assign(&mut self, rhs: Self) {
-        let mut v: Vec<T> = vec![T::zero(); self.degree * rhs.degree + 1];
-        for i in 0..=(self.degree * rhs.degree) {
-            for j in 0..=i {
-                if i - j <= rhs.degree && j <= self.degree {
-                    v[i] += self.coefficients[j] * rhs.coefficients[i - j];
-                }
+        *self *= &rhs;
+    }
+}
+
+/// Implementation of ```MulAssign``` by reference.
+impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Eq>
+    MulAssign<&Polynomial<T>> for Polynomial<T>
+{
+    /// Overloading the operator ```*=```.
+    fn mul_assign(&mut self, rhs: &Self) {
+        let mut v: Vec<T> = vec![T::zero(); self.degree + rhs.degree + 1];
+        for (i, &c) in self.coefficients.iter().enumerate() {
+            for (j, &d) in rhs.coefficients.iter().enumerate() {
+                v[i + j] += c * d;
             }
         }
         *self = Polynomial::new(&v)
     }
 }
 
+/// Implementation of ```Div```.
+impl<
+        T: Zero
+            + Identity
+            + Inverse
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+    > Div for Polynomial<T>
+{
+    type Output = Self;
+    /// Overloading the operator ```/```, i.e. the quotient of ```div_rem```.
+    /// This function panics if the leading coefficient of ```rhs``` is not invertible (e.g. ```rhs = 0```).
+    fn div(self, rhs: Self) -> Self {
+        self.div_rem(&rhs)
+            .expect("the leading coefficient of the divisor is not invertible")
+            .0
+    }
+}
+
+/// Implementation of ```DivAssign```.
+impl<
+        T: Zero
+            + Identity
+            + Inverse
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+    > DivAssign for Polynomial<T>
+{
+    /// Overloading the operator ```/=```.
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.clone() / rhs;
+    }
+}
+
+/// Implementation of ```Rem```.
+impl<
+        T: Zero
+            + Identity
+            + Inverse
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+    > Rem for Polynomial<T>
+{
+    type Output = Self;
+    /// Overloading the operator ```%```, i.e. the remainder of ```div_rem```.
+    /// This function panics if the leading coefficient of ```rhs``` is not invertible (e.g. ```rhs = 0```).
+    fn rem(self, rhs: Self) -> Self {
+        self.div_rem(&rhs)
+            .expect("the leading coefficient of the divisor is not invertible")
+            .1
+    }
+}
+
+/// Implementation of ```RemAssign```.
+impl<
+        T: Zero
+            + Identity
+            + Inverse
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+    > RemAssign for Polynomial<T>
+{
+    /// Overloading the operator ```%=```.
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = self.clone() % rhs;
+    }
+}
+
 /// Implementation of ```Neg```.
 impl<T: Zero + Eq + Copy + Neg<Output = T>> Neg for Polynomial<T> {
     type Output = Self;
@@ -348,6 +970,13 @@ impl<T: Zero + Copy> Zero for Polynomial<T> {
     }
 }
 
+/// Implementation of ```Default```, returning the zero polynomial.
+impl<T: Zero + Copy> Default for Polynomial<T> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
 /// Implementation of ```Identity``` defined in ```identities.rs```.
 impl<T: Identity + Copy> Identity for Polynomial<T> {
     /// A function that returns an object corresponding to ```1``` in the ring of polynomials.
@@ -358,3 +987,436 @@ impl<T: Identity + Copy> Identity for Polynomial<T> {
         }
     }
 }
+
+/// Implementation of ```Characteristic``` defined in ```characteristic.rs```.
+impl<T: Characteristic> Characteristic for Polynomial<T> {
+    /// The characteristic of the coefficient ring.
+    fn characteristic() -> u64 {
+        T::characteristic()
+    }
+}
+
+/// Implementation of ```num_traits::Zero```, delegating to ```Zero``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl<T: Copy + Add<Output = T> + Zero + Eq> num_traits::Zero for Polynomial<T> {
+    fn zero() -> Self {
+        <Self as Zero>::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.strict_deg().is_none()
+    }
+}
+
+/// Implementation of ```num_traits::One```, delegating to ```Identity``` defined in ```identities.rs```.
+#[cfg(feature = "num-traits")]
+impl<T: Copy + Add<Output = T> + AddAssign<T> + Mul<Output = T> + Zero + Identity + Eq>
+    num_traits::One for Polynomial<T>
+{
+    fn one() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    type F = ModInt<863>;
+
+    fn poly(coefficients: &[u64]) -> Polynomial<F> {
+        let v: Vec<F> = coefficients.iter().map(|&c| F::new(c)).collect();
+        Polynomial::new(&v)
+    }
+
+    #[test]
+    fn squarefree_part_removes_repeated_factors() {
+        // (x - 1)^2 (x - 2) has the squarefree part (x - 1)(x - 2).
+        let f = poly(&[862, 1]) * poly(&[862, 1]) * poly(&[861, 1]);
+        assert_eq!(f.squarefree_part(), poly(&[862, 1]) * poly(&[861, 1]));
+        assert_eq!(
+            f.squarefree_decomposition(),
+            vec![(poly(&[861, 1]), 1), (poly(&[862, 1]), 2)]
+        );
+    }
+
+    #[test]
+    fn squarefree_decomposition_of_pth_power_over_extension() {
+        // (x - i)^3 = x^3 + i over F_9, whose cube root needs the p-th roots of the coefficients.
+        type C = Complex<ModInt<3>>;
+        let i = C::new(ModInt::new(0), ModInt::new(1));
+        let g = Polynomial::new(&[-i, C::identity()]);
+        let f = g.clone() * g.clone() * g.clone();
+        assert_eq!(
+            f,
+            Polynomial::new(&[i, C::zero(), C::zero(), C::identity()])
+        );
+        assert_eq!(f.squarefree_decomposition(), vec![(g, 3)]);
+    }
+
+    #[test]
+    fn polynomials_satisfy_ring_axioms() {
+        fn random(rng: &mut SmallRng) -> Polynomial<F> {
+            let n = rng.gen_range(0, 12);
+            Polynomial::new(
+                &(0..n)
+                    .map(|_| F::new(rng.gen_range(0, 863)))
+                    .collect::<Vec<_>>(),
+            )
+        }
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (zero, one) = (Polynomial::<F>::zero(), Polynomial::<F>::identity());
+        for _ in 0..300 {
+            let (f, g, h) = (random(&mut rng), random(&mut rng), random(&mut rng));
+            assert_eq!(
+                (f.clone() + g.clone()) + h.clone(),
+                f.clone() + (g.clone() + h.clone())
+            );
+            assert_eq!(
+                (f.clone() * g.clone()) * h.clone(),
+                f.clone() * (g.clone() * h.clone())
+            );
+            assert_eq!(f.clone() + g.clone(), g.clone() + f.clone());
+            assert_eq!(f.clone() * g.clone(), g.clone() * f.clone());
+            assert_eq!(
+                f.clone() * (g.clone() + h.clone()),
+                f.clone() * g.clone() + f.clone() * h.clone()
+            );
+            assert_eq!(f.clone() + zero.clone(), f);
+            assert_eq!(f.clone() * one.clone(), f);
+            assert_eq!(f.clone() + -f.clone(), zero);
+            assert_eq!(f.clone() - g.clone(), f.clone() + -g.clone());
+            if f != zero && g != zero {
+                assert_eq!((f.clone() * g.clone()).deg(), f.deg() + g.deg());
+            }
+        }
+    }
+
+    #[test]
+    fn horner_evaluation_matches_sum_of_powers() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 15);
+            let f = Polynomial::new(
+                &(0..n)
+                    .map(|_| F::new(rng.gen_range(0, 863)))
+                    .collect::<Vec<_>>(),
+            );
+            let t = F::new(rng.gen_range(0, 863));
+            let mut expected = F::zero();
+            let mut t_pow = F::identity();
+            for &c in f.coefficients.iter() {
+                expected += c * t_pow;
+                t_pow *= t;
+            }
+            assert_eq!(f.eval(t), expected);
+            assert_eq!(Polynomial::evaluate(&f, t), expected);
+            assert_eq!(
+                Polynomial::evaluate_iter(f.coefficients.iter().rev().copied(), t),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn signed_string_shows_negative_coefficients_with_minus() {
+        assert_eq!(poly(&[862, 0, 1]).to_signed_string(), "-1 + x^2");
+        assert_eq!(poly(&[3, 861, 5]).to_signed_string(), "3 - 2x + 5x^2");
+        assert_eq!(poly(&[0, 862]).to_signed_string(), "-x");
+        assert_eq!(poly(&[0]).to_signed_string(), "0");
+        assert_eq!(poly(&[3, 861, 5]).print_signed_f_of_x('t'), "3 - 2t + 5t^2");
+        assert_eq!(poly(&[862, 0, 1]).to_string(), "862 + x^2");
+    }
+
+    #[test]
+    fn division_operators_match_div_rem() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        for _ in 0..100 {
+            let f = Polynomial::new(
+                &(0..12)
+                    .map(|_| F::new(rng.gen_range(0, 863)))
+                    .collect::<Vec<_>>(),
+            );
+            let mut g = Polynomial::new(
+                &(0..5)
+                    .map(|_| F::new(rng.gen_range(0, 863)))
+                    .collect::<Vec<_>>(),
+            );
+            if g == Polynomial::zero() {
+                g = Polynomial::identity();
+            }
+            let (q, r) = f.div_rem(&g).unwrap();
+            assert_eq!(f.clone() / g.clone(), q);
+            assert_eq!(f.clone() % g.clone(), r);
+            let mut h = f.clone();
+            h /= g.clone();
+            assert_eq!(h, q);
+            let mut h = f.clone();
+            h %= g.clone();
+            assert_eq!(h, r);
+            assert_eq!(q * g + r, f);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn division_by_zero_panics() {
+        let _ = poly(&[1, 2]) / Polynomial::zero();
+    }
+
+    #[test]
+    fn extended_gcd_satisfies_bezout_identity() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let common = poly(&[5, 1]);
+        for i in 0..100 {
+            let mut f = Polynomial::new(
+                &(0..8)
+                    .map(|_| F::new(rng.gen_range(0, 863)))
+                    .collect::<Vec<_>>(),
+            );
+            let mut g = Polynomial::new(
+                &(0..6)
+                    .map(|_| F::new(rng.gen_range(0, 863)))
+                    .collect::<Vec<_>>(),
+            );
+            if i % 2 == 0 {
+                f *= common.clone();
+                g *= common.clone();
+            }
+            let (d, s, t) = f.gcd_extended(&g);
+            assert_eq!(s * f.clone() + t * g.clone(), d);
+            assert_eq!(d, f.gcd(&g));
+            if i % 2 == 0 {
+                assert_eq!(d.clone() % common.clone(), Polynomial::zero());
+            }
+        }
+    }
+
+    #[test]
+    fn characteristic_is_that_of_coefficients() {
+        assert_eq!(Polynomial::<F>::characteristic(), 863);
+        assert_eq!(Polynomial::<Complex<ModInt<11>>>::characteristic(), 11);
+    }
+
+    #[test]
+    fn truncate_and_normalize() {
+        let f = poly(&[1, 1, 0, 1]);
+        assert_eq!(f.truncate(1), poly(&[1, 1]));
+        assert_eq!(f.truncate(2), poly(&[1, 1]));
+        assert_eq!(f.truncate(2).deg(), 1);
+        assert_eq!(f.truncate(10), f);
+        assert_eq!(f.truncate(0), poly(&[1]));
+        let unnormalized = Polynomial {
+            coefficients: vec![F::new(1), F::new(0), F::new(0)],
+            degree: 2,
+        };
+        assert_eq!(unnormalized.normalized(), poly(&[1]));
+        assert_eq!(unnormalized.normalized().deg(), 0);
+    }
+
+    #[test]
+    fn resultant_vanishes_exactly_for_common_roots() {
+        assert_eq!(poly(&[860, 1]).resultant(&poly(&[858, 1])), -F::new(2));
+        assert_eq!(poly(&[1, 0, 1]).resultant(&poly(&[861, 1])), F::new(5));
+        let mut rng = SmallRng::seed_from_u64(4);
+        for i in 0..100 {
+            let mut f = Polynomial::new(&(0..5).map(|_| rng.gen::<F>()).collect::<Vec<_>>());
+            let mut g = Polynomial::new(&(0..4).map(|_| rng.gen::<F>()).collect::<Vec<_>>());
+            if i % 2 == 0 {
+                let root = poly(&[rng.gen_range(0, 863), 1]);
+                f *= root.clone();
+                g *= root;
+            }
+            let common = f.gcd(&g).deg() > 0;
+            assert_eq!(f.resultant(&g) == F::zero(), common);
+        }
+    }
+
+    #[test]
+    fn integration_is_inverse_to_differentiation() {
+        let mut rng = SmallRng::seed_from_u64(5);
+        for _ in 0..50 {
+            let n = rng.gen_range(0, 20);
+            let f = Polynomial::new(&(0..n).map(|_| rng.gen::<F>()).collect::<Vec<_>>());
+            let g = f.integrate().unwrap();
+            assert_eq!(g.derivative(), f);
+            assert_eq!(g.eval(F::zero()), F::zero());
+        }
+        // x^6 / 7 does not exist over F_7.
+        let f = Polynomial::new(&[0, 0, 0, 0, 0, 0, 1].map(ModInt::<7>::new));
+        assert_eq!(f.integrate(), None);
+        assert!(Polynomial::new(&[1, 2, 3, 4, 5].map(ModInt::<7>::new))
+            .integrate()
+            .is_some());
+    }
+
+    #[test]
+    fn primitive_part_is_monic() {
+        let f = poly(&[4, 0, 2]);
+        let g = f.primitive_part();
+        assert_eq!(g, poly(&[2, 0, 1]));
+        assert_eq!(g.leading_coefficient(), F::identity());
+        assert_eq!(f.content(), F::new(2));
+        assert_eq!(g * Polynomial::new(&[f.content()]), f);
+        assert_eq!(Polynomial::<F>::zero().primitive_part(), Polynomial::zero());
+    }
+
+    #[test]
+    fn factorization_over_f863() {
+        // The factors are sorted by the degrees and then by the constant terms, where 862 = -1.
+        let sorted_factors = |f: &Polynomial<F>| {
+            let mut factors = f.factor();
+            factors.sort_by_key(|(g, _)| (g.degree, Polynomial::evaluate(g, F::zero()).to_int()));
+            factors
+        };
+        let x_squared_minus_one = poly(&[862, 0, 1]);
+        assert_eq!(
+            sorted_factors(&x_squared_minus_one),
+            vec![(poly(&[1, 1]), 1), (poly(&[862, 1]), 1)]
+        );
+        assert_eq!(
+            sorted_factors(&poly(&[0, 862, 0, 1])),
+            vec![(poly(&[0, 1]), 1), (poly(&[1, 1]), 1), (poly(&[862, 1]), 1)]
+        );
+        // -1 is not a square modulo 863, since 863 = 3 mod 4.
+        let irreducible = poly(&[1, 0, 1]);
+        assert_eq!(irreducible.factor(), vec![(irreducible.clone(), 1)]);
+        let f = irreducible.clone() * x_squared_minus_one * poly(&[1, 1]);
+        assert_eq!(
+            sorted_factors(&f),
+            vec![(poly(&[1, 1]), 2), (poly(&[862, 1]), 1), (irreducible, 1)]
+        );
+    }
+
+    #[test]
+    fn assignment_by_reference_matches_by_value() {
+        let fs = [
+            poly(&[1, 2, 3]),
+            poly(&[5]),
+            poly(&[0, 862, 0, 1]),
+            Polynomial::zero(),
+        ];
+        for f in fs.iter() {
+            for g in fs.iter() {
+                let mut by_ref = f.clone();
+                by_ref += g;
+                assert_eq!(by_ref, f.clone() + g.clone());
+                let mut by_ref = f.clone();
+                by_ref -= g;
+                assert_eq!(by_ref, f.clone() - g.clone());
+                let mut by_ref = f.clone();
+                by_ref *= g;
+                assert_eq!(by_ref, f.clone() * g.clone());
+                // The operand is only borrowed, so it can be used again.
+                by_ref += g;
+                assert_eq!(by_ref, f.clone() * g.clone() + g.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn default_is_zero() {
+        assert_eq!(Polynomial::<F>::default(), Polynomial::zero());
+    }
+
+    #[test]
+    fn streaming_evaluation_of_a_lazy_polynomial() {
+        // 1 + x + ... + x^(n - 1) = (x^n - 1) / (x - 1), whose coefficients are never stored.
+        let n = 1_000_000;
+        let t = F::new(5);
+        let expected = (t.power(n) - F::identity()) * (t - F::identity()).inverse().unwrap();
+        assert_eq!(
+            Polynomial::evaluate_iter(core::iter::repeat_n(F::identity(), n as usize), t),
+            expected
+        );
+        let f = poly(&[3, 1, 4, 1, 5]);
+        assert_eq!(
+            Polynomial::evaluate_iter(vec![5, 1, 4, 1, 3].into_iter().map(F::new), t),
+            Polynomial::evaluate(&f, t)
+        );
+        assert_eq!(Polynomial::evaluate_iter(core::iter::empty(), t), F::zero());
+    }
+
+    #[test]
+    fn shift_and_division_by_powers_of_x() {
+        let x_plus_one = poly(&[1, 1]);
+        assert_eq!(x_plus_one.shift(2), poly(&[0, 0, 1, 1]));
+        assert_eq!(x_plus_one.shift(0), x_plus_one);
+        assert_eq!(Polynomial::<F>::zero().shift(3), Polynomial::zero());
+        assert_eq!(
+            x_plus_one.shift(2).divide_by_x_pow(2),
+            Some(x_plus_one.clone())
+        );
+        assert_eq!(x_plus_one.shift(2).divide_by_x_pow(3), None);
+        assert_eq!(x_plus_one.divide_by_x_pow(1), None);
+        assert_eq!(x_plus_one.divide_by_x_pow(5), None);
+        assert_eq!(
+            Polynomial::<F>::zero().divide_by_x_pow(4),
+            Some(Polynomial::zero())
+        );
+    }
+
+    #[test]
+    fn karatsuba_matches_schoolbook() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        let mut random_poly =
+            |n: usize| Polynomial::new(&(0..n).map(|_| rng.gen::<F>()).collect::<Vec<_>>());
+        for &(m, n) in [
+            (1, 1),
+            (10, 200),
+            (31, 33),
+            (64, 64),
+            (100, 150),
+            (300, 257),
+        ]
+        .iter()
+        {
+            let (f, g) = (random_poly(m), random_poly(n));
+            assert_eq!(f.karatsuba_mul(&g), f.clone() * g.clone());
+            assert_eq!(g.karatsuba_mul(&f), f.clone() * g.clone());
+        }
+        let f = random_poly(100);
+        assert_eq!(f.karatsuba_mul(&Polynomial::zero()), Polynomial::zero());
+    }
+
+    #[test]
+    fn cubic_over_base_field_evaluated_in_extension() {
+        // x^3 + 2x + 3 at t = 1 + 2i is -6 + 2i, since i^2 = -1.
+        let f = poly(&[3, 2, 0, 1]);
+        let t = Complex::new(F::new(1), F::new(2));
+        let expected = t * t * t + t * Complex::from(F::new(2)) + Complex::from(F::new(3));
+        assert_eq!(f.evaluate_in(t), expected);
+        assert_eq!(f.evaluate_in(t), Complex::new(F::new(863 - 6), F::new(2)));
+        // At an element of the base field, it agrees with evaluate.
+        let s = F::new(5);
+        assert_eq!(
+            f.evaluate_in(Complex::from(s)),
+            Complex::from(Polynomial::evaluate(&f, s))
+        );
+    }
+
+    #[test]
+    fn evaluation_with_derivative_matches_separate_evaluations() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 12);
+            let f = Polynomial::new(&(0..n).map(|_| rng.gen::<F>()).collect::<Vec<_>>());
+            let t: F = rng.gen();
+            assert_eq!(
+                f.eval_with_derivative(t),
+                (
+                    Polynomial::evaluate(&f, t),
+                    Polynomial::evaluate(&f.derivative(), t)
+                )
+            );
+        }
+        // (x - 3)^2 has the double root 3, where both the value and the derivative vanish.
+        let f = poly(&[9, 857, 1]);
+        assert_eq!(f.eval_with_derivative(F::new(3)), (F::zero(), F::zero()));
+        assert_eq!(f.eval_with_derivative(F::new(4)), (F::new(1), F::new(2)));
+    }
+}
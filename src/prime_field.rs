@@ -0,0 +1,10 @@
+use crate::characteristic::Characteristic;
+
+/// Prime fields ```F_p = Z / (p)Z``` whose elements are represented by the integers in ```[0, p)```.
+pub trait PrimeField: Characteristic {
+    /// Make an element from an integer, reducing it modulo ```p```.
+    fn from_int(n: u64) -> Self;
+
+    /// The representative in ```[0, p)```.
+    fn to_int(&self) -> u64;
+}
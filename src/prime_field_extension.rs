@@ -0,0 +1,373 @@
+use crate::characteristic::Characteristic;
+use crate::field_size::FieldSize;
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::polynomial::Polynomial;
+use crate::signed::Signed;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// The modulus of ```PrimeFieldExtension```, i.e. a monic irreducible polynomial
+/// ```f(x) = x^N + c_(N-1) x^(N-1) + ... + c_0``` over ```F_p```.
+///
+/// Implement it for a marker type, e.g. for ```F_(2^3) = F_2[x]/(x^3 + x + 1)```:
+/// ```text
+/// struct X3X1;
+///
+/// impl ExtensionModulus<ModInt<2>, 3> for X3X1 {
+///     fn coefficients() -> [ModInt<2>; 3] {
+///         [ModInt::new(1), ModInt::new(1), ModInt::new(0)]
+///     }
+/// }
+/// ```
+pub trait ExtensionModulus<T, const N: usize> {
+    /// The coefficients ```[c_0, c_1, ..., c_(N-1)]``` of ```f``` except the leading one.
+    fn coefficients() -> [T; N];
+}
+
+/// Elements of ```F_p[x]/(f)``` of degree ```N``` over ```F_p```,
+/// where ```F_p``` consists of the objects of type ```T``` and ```f``` is given by ```M```.
+///
+/// An element is represented by the coefficients of the remainder of degree less than ```N``` starting with 0-th coefficient.
+/// It is a field if ```f``` is irreducible. Unlike ```Polynomial```, it is ```Copy```.
+pub struct PrimeFieldExtension<T, M, const N: usize> {
+    coefficients: [T; N],
+    modulus: PhantomData<M>,
+}
+
+// The traits below are implemented by hand instead of derived, so that the marker type M needs none of them.
+
+/// Implementation of ```Debug```.
+impl<T: fmt::Debug, M, const N: usize> fmt::Debug for PrimeFieldExtension<T, M, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PrimeFieldExtension")
+            .field("coefficients", &self.coefficients)
+            .finish()
+    }
+}
+
+/// Implementation of ```Clone```.
+impl<T: Copy, M, const N: usize> Clone for PrimeFieldExtension<T, M, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Implementation of ```Copy```.
+impl<T: Copy, M, const N: usize> Copy for PrimeFieldExtension<T, M, N> {}
+
+/// Implementation of ```PartialEq```.
+impl<T: PartialEq, M, const N: usize> PartialEq for PrimeFieldExtension<T, M, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients == other.coefficients
+    }
+}
+
+/// Implementation of ```Eq```.
+impl<T: Eq, M, const N: usize> Eq for PrimeFieldExtension<T, M, N> {}
+
+/// Implementation of ```Hash```.
+impl<T: Hash, M, const N: usize> Hash for PrimeFieldExtension<T, M, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.coefficients.hash(state);
+    }
+}
+
+impl<T, M, const N: usize> PrimeFieldExtension<T, M, N> {
+    /// Constructor from the coefficients of a polynomial of degree less than ```N```.
+    pub fn new(coefficients: [T; N]) -> Self {
+        Self {
+            coefficients,
+            modulus: PhantomData,
+        }
+    }
+
+    /// The coefficients starting with 0-th coefficient.
+    pub fn coefficients(&self) -> &[T; N] {
+        &self.coefficients
+    }
+}
+
+impl<T: Zero + Copy + Eq, M, const N: usize> PrimeFieldExtension<T, M, N> {
+    /// Make it from a polynomial of degree less than ```N```.
+    fn from_polynomial(f: &Polynomial<T>) -> Self {
+        let mut coefficients = [T::zero(); N];
+        for (c, &d) in coefficients.iter_mut().zip(f.coefficients()) {
+            *c = d;
+        }
+        Self::new(coefficients)
+    }
+
+    /// Regard it as a polynomial of degree less than ```N```.
+    pub fn to_polynomial(&self) -> Polynomial<T> {
+        Polynomial::new(&self.coefficients)
+    }
+}
+
+impl<T: Zero + Identity + Copy + Eq, M: ExtensionModulus<T, N>, const N: usize>
+    PrimeFieldExtension<T, M, N>
+{
+    /// The modulus ```f``` as a polynomial of degree ```N```.
+    pub fn modulus() -> Polynomial<T> {
+        let mut v = M::coefficients().to_vec();
+        v.push(T::identity());
+        Polynomial::new(&v)
+    }
+}
+
+/// Implementation of ```Display```.
+impl<
+        T: fmt::Display + Zero + Identity + Eq + Copy + Neg<Output = T> + Signed,
+        M,
+        const N: usize,
+    > fmt::Display for PrimeFieldExtension<T, M, N>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self.to_polynomial().print_signed_f_of_x('α');
+        if self.to_polynomial().deg() == 0 {
+            write!(f, "{}", s)
+        } else {
+            write!(f, "({})", s)
+        }
+    }
+}
+
+/// Implementation of ```Add```.
+impl<T: Copy + Add<Output = T>, M, const N: usize> Add for PrimeFieldExtension<T, M, N> {
+    type Output = Self;
+    /// Overloading the operator ```+```.
+    fn add(self, rhs: Self) -> Self {
+        let mut coefficients = self.coefficients;
+        for (c, &d) in coefficients.iter_mut().zip(rhs.coefficients.iter()) {
+            *c = *c + d;
+        }
+        Self::new(coefficients)
+    }
+}
+
+/// Implementation of ```AddAssign```.
+impl<T: Copy + Add<Output = T>, M, const N: usize> AddAssign for PrimeFieldExtension<T, M, N> {
+    /// Overloading the operator ```+=```.
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Implementation of ```Sub```.
+impl<T: Copy + Sub<Output = T>, M, const N: usize> Sub for PrimeFieldExtension<T, M, N> {
+    type Output = Self;
+    /// Overloading the operator ```-```.
+    fn sub(self, rhs: Self) -> Self {
+        let mut coefficients = self.coefficients;
+        for (c, &d) in coefficients.iter_mut().zip(rhs.coefficients.iter()) {
+            *c = *c - d;
+        }
+        Self::new(coefficients)
+    }
+}
+
+/// Implementation of ```SubAssign```.
+impl<T: Copy + Sub<Output = T>, M, const N: usize> SubAssign for PrimeFieldExtension<T, M, N> {
+    /// Overloading the operator ```-=```.
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Implementation of ```Mul```.
+impl<
+        T: Copy + Zero + Add<Output = T> + Mul<Output = T> + Sub<Output = T>,
+        M: ExtensionModulus<T, N>,
+        const N: usize,
+    > Mul for PrimeFieldExtension<T, M, N>
+{
+    type Output = Self;
+    /// Overloading the operator ```*```.
+    ///
+    /// The product of polynomials is reduced by ```x^N = -(c_(N-1) x^(N-1) + ... + c_0)``` from the highest degree.
+    fn mul(self, rhs: Self) -> Self {
+        if N == 0 {
+            return self;
+        }
+        let mut v: Vec<T> = vec![T::zero(); 2 * N - 1];
+        for (i, &c) in self.coefficients.iter().enumerate() {
+            for (j, &d) in rhs.coefficients.iter().enumerate() {
+                v[i + j] = v[i + j] + c * d;
+            }
+        }
+        let f = M::coefficients();
+        for k in (N..2 * N - 1).rev() {
+            let c = v[k];
+            for (i, &d) in f.iter().enumerate() {
+                v[k - N + i] = v[k - N + i] - c * d;
+            }
+        }
+        let mut coefficients = [T::zero(); N];
+        coefficients.copy_from_slice(&v[..N]);
+        Self::new(coefficients)
+    }
+}
+
+/// Implementation of ```MulAssign```.
+impl<
+        T: Copy + Zero + Add<Output = T> + Mul<Output = T> + Sub<Output = T>,
+        M: ExtensionModulus<T, N>,
+        const N: usize,
+    > MulAssign for PrimeFieldExtension<T, M, N>
+{
+    /// Overloading the operator ```*=```.
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// Implementation of ```Neg```.
+impl<T: Copy + Neg<Output = T>, M, const N: usize> Neg for PrimeFieldExtension<T, M, N> {
+    type Output = Self;
+    /// Overloading the operator ```-```.
+    fn neg(self) -> Self {
+        let mut coefficients = self.coefficients;
+        for c in coefficients.iter_mut() {
+            *c = -*c;
+        }
+        Self::new(coefficients)
+    }
+}
+
+/// Implementation of ```Zero``` defined in ```identities.rs```.
+impl<T: Copy + Zero, M, const N: usize> Zero for PrimeFieldExtension<T, M, N> {
+    /// A function that returns an object corresponding to ```0``` in ```F_p[x]/(f)```.
+    fn zero() -> Self {
+        Self::new([T::zero(); N])
+    }
+}
+
+/// Implementation of ```Default```, returning ```0```.
+impl<T: Copy + Zero, M, const N: usize> Default for PrimeFieldExtension<T, M, N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// Implementation of ```Identity``` defined in ```identities.rs```.
+impl<T: Copy + Zero + Identity, M, const N: usize> Identity for PrimeFieldExtension<T, M, N> {
+    /// A function that returns an object corresponding to ```1``` in ```F_p[x]/(f)```.
+    fn identity() -> Self {
+        let mut coefficients = [T::zero(); N];
+        coefficients[0] = T::identity();
+        Self::new(coefficients)
+    }
+}
+
+/// Implementation of ```Inverse``` defined in ```inverse.rs```.
+impl<
+        T: Zero
+            + Identity
+            + Inverse
+            + Characteristic
+            + Add<Output = T>
+            + AddAssign
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Copy
+            + Eq,
+        M: ExtensionModulus<T, N>,
+        const N: usize,
+    > Inverse for PrimeFieldExtension<T, M, N>
+{
+    /// A function that returns an object corresponding to ```x^(-1)``` in ```F_p[x]/(f)```.
+    ///
+    /// It is computed by the extended Euclidean algorithm: if ```s g + t f = 1```, then ```g^(-1) = s mod f```.
+    /// It returns ```None``` unless ```gcd(g, f) = 1```, e.g. for ```0```.
+    fn inverse(self) -> Option<Self> {
+        let f = Self::modulus();
+        let (g, s, _) = self.to_polynomial().gcd_extended(&f);
+        if g != Polynomial::identity() {
+            return None;
+        }
+        let (_, r) = s.div_rem(&f)?;
+        Some(Self::from_polynomial(&r))
+    }
+}
+
+/// Implementation of ```Signed``` defined in ```signed.rs```.
+/// No element is regarded as negative.
+impl<T, M, const N: usize> Signed for PrimeFieldExtension<T, M, N> {}
+
+/// Implementation of ```Characteristic``` defined in ```characteristic.rs```.
+impl<T: Characteristic, M, const N: usize> Characteristic for PrimeFieldExtension<T, M, N> {
+    fn characteristic() -> u64 {
+        T::characteristic()
+    }
+}
+
+/// Implementation of ```FieldSize``` defined in ```field_size.rs```.
+/// This is meaningful when ```f``` is irreducible.
+impl<T: FieldSize, M, const N: usize> FieldSize for PrimeFieldExtension<T, M, N> {
+    /// The degree is multiplied by ```N = deg f```.
+    fn degree() -> u32 {
+        N as u32 * T::degree()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modint::ModInt;
+
+    /// ```F_(2^3) = F_2[x]/(x^3 + x + 1)```.
+    struct X3X1;
+
+    impl ExtensionModulus<ModInt<2>, 3> for X3X1 {
+        fn coefficients() -> [ModInt<2>; 3] {
+            [ModInt::new(1), ModInt::new(1), ModInt::new(0)]
+        }
+    }
+
+    type F8 = PrimeFieldExtension<ModInt<2>, X3X1, 3>;
+
+    fn f8(n: u64) -> F8 {
+        F8::new([
+            ModInt::new(n & 1),
+            ModInt::new((n >> 1) & 1),
+            ModInt::new((n >> 2) & 1),
+        ])
+    }
+
+    #[test]
+    fn arithmetic_in_f8() {
+        let x = f8(0b010);
+        assert_eq!(x * x, f8(0b100));
+        assert_eq!(x * x * x, f8(0b011));
+        assert_eq!(f8(0b101) + f8(0b110), f8(0b011));
+        assert_eq!(F8::field_order(), 8);
+        for n in 1..8 {
+            let y = f8(n);
+            assert_eq!(y * y.inverse().unwrap(), F8::identity());
+            let mut power = F8::identity();
+            for _ in 0..7 {
+                power *= y;
+            }
+            assert_eq!(power, F8::identity());
+        }
+        assert_eq!(f8(0).inverse(), None);
+    }
+
+    #[test]
+    fn f8_satisfies_field_axioms() {
+        for x in (0..8).map(f8) {
+            assert_eq!(x + -x, F8::zero());
+            for y in (0..8).map(f8) {
+                assert_eq!(x * y, y * x);
+                for z in (0..8).map(f8) {
+                    assert_eq!((x * y) * z, x * (y * z));
+                    assert_eq!(x * (y + z), x * y + x * z);
+                }
+            }
+        }
+    }
+}
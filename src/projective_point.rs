@@ -0,0 +1,362 @@
+use crate::ct_eq::ConditionallySelectable;
+use crate::field::Field;
+use crate::identities::{Identity, Zero};
+use crate::rational_point::RationalPoint;
+
+/// Rational points on an elliptic curve ```y^2 = x^3 + ax + b``` in homogeneous projective coordinates.
+///
+/// ```(X : Y : Z)``` with ```Z != 0``` is corresponding to the affine point ```(X/Z, Y/Z)```
+/// and ```(X : Y : Z)``` with ```Z = 0``` is corresponding to the point at infinity ```O```.
+/// Unlike ```RationalPoint```, additions and doublings in these coordinates need no inversion.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectivePoint<T> {
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T> ProjectivePoint<T> {
+    /// Constructor.
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Zero + Identity + Eq> ProjectivePoint<T> {
+    /// The point at infinity ```(0 : 1 : 0)```.
+    pub fn infinity() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::identity(),
+            z: T::zero(),
+        }
+    }
+
+    /// Make it from an affine point, i.e. ```(x, y) -> (x : y : 1)```.
+    pub fn from_affine(p: &RationalPoint<T>) -> Self {
+        match *p {
+            RationalPoint::O => Self::infinity(),
+            RationalPoint::Point(x, y) => Self {
+                x,
+                y,
+                z: T::identity(),
+            },
+        }
+    }
+
+    /// Check whether it is the point at infinity.
+    pub fn is_infinity(&self) -> bool {
+        self.z == T::zero()
+    }
+}
+
+impl<T: Field> ProjectivePoint<T> {
+    /// Make it affine. Only this function needs an inversion.
+    pub fn to_affine(&self) -> RationalPoint<T> {
+        if self.is_infinity() {
+            RationalPoint::O
+        } else {
+            let inv = self.z.inverse().unwrap();
+            RationalPoint::Point(self.x * inv, self.y * inv)
+        }
+    }
+
+    /// Check whether both represent the same point, i.e. ```(X1 : Y1 : Z1) = (X2 : Y2 : Z2)``` up to scaling.
+    ///
+    /// It compares ```X1 Z2 = X2 Z1``` and ```Y1 Z2 = Y2 Z1``` without inversions,
+    /// and regards all the points with ```Z = 0``` as the point at infinity.
+    pub fn eq_on_curve(&self, other: &Self) -> bool {
+        match (self.is_infinity(), other.is_infinity()) {
+            (true, true) => true,
+            (false, false) => {
+                self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+            }
+            _ => false,
+        }
+    }
+
+    /// A doubling of a point on an elliptic curve.
+    pub fn double(&self, a: T) -> Self {
+        if self.is_infinity() {
+            return *self;
+        }
+        let id = T::identity();
+        let two = id + id;
+        let xx = self.x * self.x;
+        let w = a * self.z * self.z + (two + id) * xx;
+        let s = two * self.y * self.z;
+        let ss = s * s;
+        let r = self.y * s;
+        let rr = r * r;
+        let b = (self.x + r) * (self.x + r) - xx - rr;
+        let h = w * w - two * b;
+        Self {
+            x: h * s,
+            y: w * (b - h) - two * rr,
+            z: s * ss,
+        }
+    }
+
+    /// An addition of points on an elliptic curve.
+    pub fn add(&self, rhs: &Self, a: T) -> Self {
+        if self.is_infinity() {
+            return *rhs;
+        }
+        if rhs.is_infinity() {
+            return *self;
+        }
+        let y1z2 = self.y * rhs.z;
+        let x1z2 = self.x * rhs.z;
+        let z1z2 = self.z * rhs.z;
+        let u = rhs.y * self.z - y1z2;
+        let v = rhs.x * self.z - x1z2;
+        if v == T::zero() {
+            return if u == T::zero() {
+                self.double(a)
+            } else {
+                Self::infinity()
+            };
+        }
+        let uu = u * u;
+        let vv = v * v;
+        let vvv = v * vv;
+        let r = vv * x1z2;
+        let s = uu * z1z2 - vvv - r - r;
+        Self {
+            x: v * s,
+            y: u * (r - s) - vvv * y1z2,
+            z: vvv * z1z2,
+        }
+    }
+
+    /// Calculate nP by repeated squaring.
+    pub fn multiply(&self, a: T, n: u64) -> Self {
+        let mut res = Self::infinity();
+        let mut now = *self;
+        let mut m = n;
+        loop {
+            if m == 0 {
+                break;
+            }
+            if m % 2 == 1 {
+                res = res.add(&now, a);
+            }
+            now = now.double(a);
+            m /= 2;
+        }
+        res
+    }
+
+    /// A complete addition of points on the curve ```y^2 = x^3 + ax + b```, where ```b3 = 3b```,
+    /// by Algorithm 1 of Renes, Costello and Batina, "Complete addition formulas for prime order elliptic curves".
+    ///
+    /// Unlike ```add```, it has no special cases: the point at infinity and ```self == rhs``` are handled
+    /// by the same field operations as the other inputs, so that it never branches on the points.
+    /// The only exceptions are the pairs whose difference is a point of order 2, for which ```(0 : 0 : 0)``` is returned.
+    pub fn add_complete(&self, rhs: &Self, a: T, b3: T) -> Self {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (rhs.x, rhs.y, rhs.z);
+        let t0 = x1 * x2;
+        let t1 = y1 * y2;
+        let t2 = z1 * z2;
+        let t3 = (x1 + y1) * (x2 + y2) - (t0 + t1);
+        let t4 = (x1 + z1) * (x2 + z2) - (t0 + t2);
+        let t5 = (y1 + z1) * (y2 + z2) - (t1 + t2);
+        let z3 = a * t4 + b3 * t2;
+        let x3 = t1 - z3;
+        let z3 = t1 + z3;
+        let y3 = x3 * z3;
+        let t1 = t0 + t0 + t0;
+        let t2 = a * t2;
+        let t4 = b3 * t4;
+        let t1 = t1 + t2;
+        let t2 = a * (t0 - t2);
+        let t4 = t4 + t2;
+        Self {
+            x: t3 * x3 - t5 * t4,
+            y: y3 + t1 * t4,
+            z: t5 * z3 + t3 * t1,
+        }
+    }
+}
+
+/// Implementation of ```ConditionallySelectable``` defined in ```ct_eq.rs```, selecting each coordinate.
+impl<T: ConditionallySelectable> ConditionallySelectable for ProjectivePoint<T> {
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        Self {
+            x: T::conditional_select(&a.x, &b.x, choice),
+            y: T::conditional_select(&a.y, &b.y, choice),
+            z: T::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+impl<T: Field + ConditionallySelectable> ProjectivePoint<T> {
+    /// Swap ```self``` and ```other``` if ```bit``` is ```true```.
+    ///
+    /// Every coordinate is selected by ```T::conditional_select``` instead of branching on ```bit```.
+    fn conditional_swap(&mut self, other: &mut Self, bit: bool) {
+        let (p, q) = (*self, *other);
+        *self = Self::conditional_select(&p, &q, bit);
+        *other = Self::conditional_select(&q, &p, bit);
+    }
+
+    /// Calculate nP by the Montgomery ladder.
+    ///
+    /// Unlike ```multiply```, which adds only for the bits equal to 1,
+    /// one addition and one doubling are performed for each of the 64 bits of ```n``` whatever the bit is,
+    /// so that the running time does not reveal the bits of a secret ```n```.
+    /// Both are done by ```add_complete```, so that every step runs the same field operations
+    /// even while the point at infinity appears for the leading zero bits of ```n```.
+    /// The coefficient ```b``` needed by it is recovered from the point itself, which is the only step depending on ```self```.
+    ///
+    /// The ladder keeps the difference of the two points equal to ```self```,
+    /// hence the exceptions of ```add_complete``` only occur if ```self``` is of order 2.
+    pub fn multiply_ct(&self, a: T, n: u64) -> Self {
+        let (x, y) = match self.to_affine() {
+            RationalPoint::O => return Self::infinity(),
+            RationalPoint::Point(x, y) => (x, y),
+        };
+        if y == T::zero() {
+            return Self::conditional_select(&Self::infinity(), self, n & 1 == 1);
+        }
+        let b = y * y - x * x * x - a * x;
+        let b3 = b + b + b;
+        let mut r0 = Self::infinity();
+        let mut r1 = *self;
+        for i in (0..64).rev() {
+            let bit = (n >> i) & 1 == 1;
+            r0.conditional_swap(&mut r1, bit);
+            r1 = r0.add_complete(&r1, a, b3);
+            r0 = r0.add_complete(&r0, a, b3);
+            r0.conditional_swap(&mut r1, bit);
+        }
+        r0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    type F = ModInt<11>;
+    type C = Complex<F>;
+
+    fn c(re: u64, im: u64) -> C {
+        Complex::new(F::new(re), F::new(im))
+    }
+
+    /// All the rational points of ```y^2 = x^3 + ax + b``` over ```F_(11^2)```, including ```O```.
+    fn all_points(a: C, b: C) -> Vec<RationalPoint<C>> {
+        let elements: Vec<C> = (0..121).map(|n| c(n % 11, n / 11)).collect();
+        let mut points = vec![RationalPoint::O];
+        for &x in elements.iter() {
+            for &y in elements.iter() {
+                if y * y == x * x * x + a * x + b {
+                    points.push(RationalPoint::Point(x, y));
+                }
+            }
+        }
+        points
+    }
+
+    /// The curves ```y^2 = x^3 + x + 4``` with no point of order 2 over ```F_(11^2)```,
+    /// since the cubic is irreducible over ```F_11```,
+    /// and ```y^2 = x^3 + x + 9 = (x - 1)(x - 4)(x - 6)``` with three of them, paired with the number of the latter.
+    fn curves() -> [(C, C, usize); 2] {
+        [(c(1, 0), c(4, 0), 0), (c(1, 0), c(9, 0), 3)]
+    }
+
+    #[test]
+    fn complete_addition_matches_affine_addition() {
+        for &(a, b, two_torsion) in curves().iter() {
+            let b3 = b + b + b;
+            let points = all_points(a, b);
+            let mut exceptions = 0;
+            for p in points.iter() {
+                for q in points.iter() {
+                    let sum = ProjectivePoint::from_affine(p).add_complete(
+                        &ProjectivePoint::from_affine(q),
+                        a,
+                        b3,
+                    );
+                    let difference = p.add_rational_points(&-*q, a);
+                    if difference.double(a) == RationalPoint::O && difference != RationalPoint::O {
+                        exceptions += 1;
+                        assert_eq!((sum.x, sum.y, sum.z), (C::zero(), C::zero(), C::zero()));
+                    } else {
+                        assert_eq!(sum.to_affine(), p.add_rational_points(q, a));
+                    }
+                }
+            }
+            assert_eq!(exceptions, two_torsion * points.len());
+        }
+    }
+
+    #[test]
+    fn ladder_matches_variable_time_multiplication() {
+        let scalars: Vec<u64> = (0..30).chain([1 << 40, u64::MAX - 1, u64::MAX]).collect();
+        for &(a, b, _) in curves().iter() {
+            for p in all_points(a, b).iter() {
+                for &n in scalars.iter() {
+                    let expected = p.multiply_affine(a, n);
+                    assert_eq!(
+                        ProjectivePoint::from_affine(p)
+                            .multiply_ct(a, n)
+                            .to_affine(),
+                        expected
+                    );
+                    assert_eq!(p.multiply_ct(a, n), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn conditional_swap_exchanges_only_for_true() {
+        let (a, b, _) = curves()[1];
+        let points: Vec<ProjectivePoint<C>> = all_points(a, b)
+            .iter()
+            .map(ProjectivePoint::from_affine)
+            .collect();
+        for p in points.iter() {
+            for q in points.iter() {
+                let (mut s, mut t) = (*p, *q);
+                s.conditional_swap(&mut t, false);
+                assert_eq!(
+                    (s.to_affine(), t.to_affine()),
+                    (p.to_affine(), q.to_affine())
+                );
+                s.conditional_swap(&mut t, true);
+                assert_eq!(
+                    (s.to_affine(), t.to_affine()),
+                    (q.to_affine(), p.to_affine())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn scaled_representatives_are_equal() {
+        let (a, b, _) = curves()[1];
+        let points = all_points(a, b);
+        for p in points.iter() {
+            let q = ProjectivePoint::from_affine(p);
+            for &t in [c(2, 0), c(3, 5), c(10, 10)].iter() {
+                let scaled = ProjectivePoint::new(q.x * t, q.y * t, q.z * t);
+                assert!(q.eq_on_curve(&scaled));
+                assert!(scaled.eq_on_curve(&q));
+                assert_eq!(scaled.to_affine(), *p);
+            }
+            for r in points.iter().filter(|&r| r != p) {
+                assert!(!q.eq_on_curve(&ProjectivePoint::from_affine(r)));
+            }
+        }
+        let infinity = ProjectivePoint::new(C::zero(), c(7, 3), C::zero());
+        assert!(infinity.eq_on_curve(&ProjectivePoint::infinity()));
+    }
+}
@@ -0,0 +1,264 @@
+use crate::characteristic::Characteristic;
+use crate::field_size::FieldSize;
+use crate::identities::{Identity, Zero};
+use crate::inverse::Inverse;
+use crate::prime_field::PrimeField;
+use crate::signed::Signed;
+use core::fmt;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Elements ```a + b√D``` in ```F_p[x]/(x^2 - D)```
+/// where ```F_p``` is a prime field consisting of the objects of type ```T```.
+///
+/// This is a generalization of ```Complex<T>```, which is the case ```D = -1```.
+/// It is a field if ```D``` is a quadratic non-residue modulo ```p```,
+/// e.g. ```D = 2``` for ```p = 5```, so that it can be used also for primes with ```p % 4 == 1```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct QuadraticExtension<T, const D: u64> {
+    a: T,
+    b: T,
+}
+
+impl<T, const D: u64> QuadraticExtension<T, D> {
+    /// Constructor of ```a + b√D```.
+    pub fn new(a: T, b: T) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<T: Copy, const D: u64> QuadraticExtension<T, D> {
+    /// The component ```a``` of ```a + b√D```.
+    pub fn a(&self) -> T {
+        self.a
+    }
+
+    /// The component ```b``` of ```a + b√D```.
+    pub fn b(&self) -> T {
+        self.b
+    }
+}
+
+impl<
+        T: PrimeField + Copy + Add<Output = T> + Mul<Output = T> + Eq + Zero + Identity,
+        const D: u64,
+    > QuadraticExtension<T, D>
+{
+    /// Culculate exponentiation by repeated squaring.
+    pub fn modpow(&self, n: u64) -> Self {
+        let mut res = Self::identity();
+        let mut a = *self;
+        let mut m = n;
+        loop {
+            if m == 0 {
+                break;
+            }
+            if m % 2 == 1 {
+                res *= a;
+            }
+            a *= a;
+            m /= 2;
+        }
+        res
+    }
+
+    /// Euler's criterion, i.e. check whether ```x^((q - 1) / 2) = 1``` with ```q = p^2```.
+    /// It returns ```false``` for ```0```.
+    pub fn is_quadratic_residue(&self) -> bool {
+        let p = T::characteristic();
+        self.modpow((p * p - 1) / 2) == Self::identity()
+    }
+}
+
+/// Implementation of ```Display```.
+impl<T: fmt::Display + Zero + Eq, const D: u64> fmt::Display for QuadraticExtension<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.b == T::zero() {
+            write!(f, "{}", self.a)
+        } else if self.a == T::zero() {
+            write!(f, "{}√{}", self.b, D)
+        } else {
+            write!(f, "({} + {}√{})", self.a, self.b, D)
+        }
+    }
+}
+
+/// Implementation of ```Add```.
+impl<T: Copy + Add<Output = T>, const D: u64> Add for QuadraticExtension<T, D> {
+    type Output = Self;
+    /// Overloading the operator ```+```.
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            a: self.a + rhs.a,
+            b: self.b + rhs.b,
+        }
+    }
+}
+
+/// Implementation of ```AddAssign```.
+impl<T: Copy + Add<Output = T>, const D: u64> AddAssign for QuadraticExtension<T, D> {
+    /// Overloading the operator ```+=```.
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Implementation of ```Sub```.
+impl<T: Copy + Sub<Output = T>, const D: u64> Sub for QuadraticExtension<T, D> {
+    type Output = Self;
+    /// Overloading the operator ```-```.
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            a: self.a - rhs.a,
+            b: self.b - rhs.b,
+        }
+    }
+}
+
+/// Implementation of ```SubAssign```.
+impl<T: Copy + Sub<Output = T>, const D: u64> SubAssign for QuadraticExtension<T, D> {
+    /// Overloading the operator ```-=```.
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Implementation of ```Mul```.
+impl<T: PrimeField + Copy + Add<Output = T> + Mul<Output = T>, const D: u64> Mul
+    for QuadraticExtension<T, D>
+{
+    type Output = Self;
+    /// Overloading the operator ```*```, i.e. ```(a + b√D)(c + e√D) = (ac + Dbe) + (ae + bc)√D```.
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            a: self.a * rhs.a + T::from_int(D) * self.b * rhs.b,
+            b: self.a * rhs.b + self.b * rhs.a,
+        }
+    }
+}
+
+/// Implementation of ```MulAssign```.
+impl<T: PrimeField + Copy + Add<Output = T> + Mul<Output = T>, const D: u64> MulAssign
+    for QuadraticExtension<T, D>
+{
+    /// Overloading the operator ```*=```.
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// Implementation of ```Neg```.
+impl<T: Neg<Output = T>, const D: u64> Neg for QuadraticExtension<T, D> {
+    type Output = Self;
+    /// Overloading the operator ```-```.
+    fn neg(self) -> Self {
+        Self {
+            a: -self.a,
+            b: -self.b,
+        }
+    }
+}
+
+/// Implementation of ```Zero``` defined in ```identities.rs```.
+impl<T: Zero, const D: u64> Zero for QuadraticExtension<T, D> {
+    /// A function that returns an object corresponding to ```0 + 0√D```.
+    fn zero() -> Self {
+        Self {
+            a: T::zero(),
+            b: T::zero(),
+        }
+    }
+}
+
+/// Implementation of ```Default```, returning ```0```.
+impl<T: Zero, const D: u64> Default for QuadraticExtension<T, D> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// Implementation of ```Identity``` defined in ```identities.rs```.
+impl<T: Zero + Identity, const D: u64> Identity for QuadraticExtension<T, D> {
+    /// A function that returns an object corresponding to ```1 + 0√D```.
+    fn identity() -> Self {
+        Self {
+            a: T::identity(),
+            b: T::zero(),
+        }
+    }
+}
+
+/// Implementation of ```Inverse``` defined in ```inverse.rs```.
+impl<
+        T: PrimeField
+            + Copy
+            + Add<Output = T>
+            + Mul<Output = T>
+            + Sub<Output = T>
+            + Neg<Output = T>
+            + Inverse,
+        const D: u64,
+    > Inverse for QuadraticExtension<T, D>
+{
+    /// A function that returns an object corresponding to ```x^(-1)```.
+    ///
+    /// It is computed as ```(a + b√D)^(-1) = (a - b√D) / (a^2 - Db^2)``` using the norm ```a^2 - Db^2``` in ```F_p```,
+    /// which is non-zero for every non-zero element if ```D``` is a quadratic non-residue.
+    fn inverse(self) -> Option<Self> {
+        let norm_inv = (self.a * self.a - T::from_int(D) * self.b * self.b).inverse()?;
+        Some(Self {
+            a: self.a * norm_inv,
+            b: -self.b * norm_inv,
+        })
+    }
+}
+
+/// Implementation of ```Signed``` defined in ```signed.rs```.
+/// No element is regarded as negative.
+impl<T, const D: u64> Signed for QuadraticExtension<T, D> {}
+
+/// Implementation of ```Characteristic``` defined in ```characteristic.rs```.
+impl<T: Characteristic, const D: u64> Characteristic for QuadraticExtension<T, D> {
+    fn characteristic() -> u64 {
+        T::characteristic()
+    }
+}
+
+/// Implementation of ```FieldSize``` defined in ```field_size.rs```.
+/// This is meaningful when ```D``` is a quadratic non-residue.
+impl<T: FieldSize, const D: u64> FieldSize for QuadraticExtension<T, D> {
+    /// The degree is doubled since ```x^2 - D``` has degree 2.
+    fn degree() -> u32 {
+        2 * T::degree()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+
+    #[test]
+    fn square_root_of_d_squares_to_d() {
+        type Q = QuadraticExtension<ModInt<13>, 2>;
+        let root = Q::new(ModInt::new(0), ModInt::new(1));
+        assert_eq!(root * root, Q::new(ModInt::new(2), ModInt::new(0)));
+        assert!(Q::new(ModInt::new(2), ModInt::new(0)).is_quadratic_residue());
+        assert_ne!(ModInt::<13>::new(2).power(6), ModInt::new(1));
+    }
+
+    #[test]
+    fn minus_one_gives_complex_multiplication() {
+        type Q = QuadraticExtension<ModInt<11>, 10>;
+        for n in 0..121 {
+            for m in (0..121).step_by(7) {
+                let (x, y) = ((n % 11, n / 11), (m % 11, m / 11));
+                let q = Q::new(ModInt::new(x.0), ModInt::new(x.1))
+                    * Q::new(ModInt::new(y.0), ModInt::new(y.1));
+                let c = Complex::new(ModInt::new(x.0), ModInt::new(x.1))
+                    * Complex::new(ModInt::<11>::new(y.0), ModInt::new(y.1));
+                assert_eq!((q.a(), q.b()), (c.real(), c.imaginary()));
+            }
+        }
+    }
+}
@@ -1,7 +1,27 @@
-use crate::identities::Identity;
-use crate::inverse::Inverse;
-use std::fmt;
-use std::ops::{Add, Mul, Neg, Sub};
+use crate::complexification::Complex;
+use crate::ct_eq::{ct_select_u64, ConditionallySelectable, ConstantTimeEq};
+use crate::elliptic_curve::cofactor;
+use crate::field::Field;
+use crate::identities::{Identity, Zero};
+use crate::modint::ModInt;
+use crate::polynomial::Polynomial;
+use crate::prime_field::PrimeField;
+use crate::projective_point::ProjectivePoint;
+#[cfg(feature = "std")]
+use crate::util::isqrt;
+use crate::util::{factorize, naf, reduce_exponent};
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub};
+use core::str::FromStr;
+use rand::Rng;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
 /// This type is intended to be treated as the type representing the rational points on some plane curves.
 ///
@@ -19,8 +39,80 @@ impl<T> RationalPoint<T> {
     pub fn new(x: T, y: T) -> Self {
         RationalPoint::Point(x, y)
     }
+
+    /// Whether it is the point at infinity ```O```.
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, RationalPoint::O)
+    }
+}
+
+/// Implementation of ```Default```, returning the point at infinity ```O```.
+impl<T> Default for RationalPoint<T> {
+    fn default() -> Self {
+        RationalPoint::O
+    }
+}
+
+/// Implementation of ```From<(T, T)>```, i.e. ```(x, y) -> RationalPoint::Point(x, y)```.
+impl<T> From<(T, T)> for RationalPoint<T> {
+    fn from((x, y): (T, T)) -> Self {
+        RationalPoint::Point(x, y)
+    }
+}
+
+impl<T: Copy> RationalPoint<T> {
+    /// The affine coordinates ```(x, y)```, or ```None``` for the point at infinity.
+    pub fn affine(&self) -> Option<(T, T)> {
+        match *self {
+            RationalPoint::O => None,
+            RationalPoint::Point(x, y) => Some((x, y)),
+        }
+    }
+}
+
+/// Errors in the arithmetic of rational points.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CurveError {
+    /// An element which had to be inverted is not invertible.
+    NotInvertible,
+}
+
+/// Implementation of ```Display```.
+impl fmt::Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CurveError::NotInvertible => write!(f, "an element is not invertible"),
+        }
+    }
+}
+
+/// Implementation of ```Error```.
+#[cfg(feature = "std")]
+impl std::error::Error for CurveError {}
+
+/// Reasons why a scalar is rejected by ```RationalPoint::multiply_checked```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ScalarError {
+    /// The scalar is ```0```.
+    Zero,
+    /// The scalar is not less than the order of the point.
+    OutOfRange,
+}
+
+/// Implementation of ```Display```.
+impl fmt::Display for ScalarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScalarError::Zero => write!(f, "the scalar is zero"),
+            ScalarError::OutOfRange => write!(f, "the scalar is not less than the order"),
+        }
+    }
 }
 
+/// Implementation of ```Error```.
+#[cfg(feature = "std")]
+impl std::error::Error for ScalarError {}
+
 /// Implementation of ```Display```.
 impl<T: fmt::Display> fmt::Display for RationalPoint<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -31,74 +123,1168 @@ impl<T: fmt::Display> fmt::Display for RationalPoint<T> {
     }
 }
 
-impl<
-        T: Add<Output = T>
-            + Mul<Output = T>
-            + Sub<Output = T>
-            + Copy
-            + Eq
-            + Inverse
-            + Identity
-            + Neg<Output = T>,
-    > RationalPoint<T>
-{
+/// Reasons why a string is rejected by ```RationalPoint::from_str```.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ParsePointError {
+    /// The string is neither ```O``` nor enclosed in parentheses.
+    MissingParentheses,
+    /// The string does not consist of two coordinates separated by a comma.
+    WrongNumberOfCoordinates,
+    /// A coordinate is not of the form ```a```, ```bi``` or ```(a + bi)``` with non-negative integers ```a``` and ```b```.
+    InvalidCoordinate,
+    /// An integer in a coordinate is not less than the modulus.
+    OutOfRange,
+}
+
+/// Implementation of ```Display```.
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePointError::MissingParentheses => {
+                write!(f, "the point is neither O nor enclosed in parentheses")
+            }
+            ParsePointError::WrongNumberOfCoordinates => {
+                write!(f, "the point does not have exactly two coordinates")
+            }
+            ParsePointError::InvalidCoordinate => write!(f, "a coordinate is malformed"),
+            ParsePointError::OutOfRange => {
+                write!(f, "an integer is not less than the modulus")
+            }
+        }
+    }
+}
+
+/// Implementation of ```Error```.
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePointError {}
+
+/// Parse an element of ```Z / (P)Z``` written as its representative.
+fn parse_modint<const P: u64>(s: &str) -> Result<ModInt<P>, ParsePointError> {
+    let n: u64 = s
+        .trim()
+        .parse()
+        .map_err(|_| ParsePointError::InvalidCoordinate)?;
+    if n >= P {
+        return Err(ParsePointError::OutOfRange);
+    }
+    Ok(ModInt::new(n))
+}
+
+/// Parse an element of ```F_(P^2)``` in the format of its ```Display```, i.e. ```a```, ```bi``` or ```(a + bi)```.
+fn parse_complex<const P: u64>(s: &str) -> Result<Complex<ModInt<P>>, ParsePointError> {
+    let s = s.trim();
+    if let Some(inner) = s.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let (re, im) = inner
+            .split_once('+')
+            .ok_or(ParsePointError::InvalidCoordinate)?;
+        let im = im
+            .trim()
+            .strip_suffix('i')
+            .ok_or(ParsePointError::InvalidCoordinate)?;
+        Ok(Complex::new(parse_modint(re)?, parse_modint(im)?))
+    } else if let Some(im) = s.strip_suffix('i') {
+        Ok(Complex::new(ModInt::zero(), parse_modint(im)?))
+    } else {
+        Ok(Complex::new(parse_modint(s)?, ModInt::zero()))
+    }
+}
+
+/// Implementation of ```FromStr```, the inverse of ```Display```,
+/// i.e. ```O``` or ```(x, y)``` where the coordinates are of the form ```a```, ```bi``` or ```(a + bi)```.
+///
+/// The whitespace around the symbols is ignored. It does not check whether the point lies on a curve.
+impl<const P: u64> FromStr for RationalPoint<Complex<ModInt<P>>> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "O" {
+            return Ok(RationalPoint::O);
+        }
+        let inner = s
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .ok_or(ParsePointError::MissingParentheses)?;
+        let (x, y) = inner
+            .split_once(',')
+            .ok_or(ParsePointError::WrongNumberOfCoordinates)?;
+        if y.contains(',') {
+            return Err(ParsePointError::WrongNumberOfCoordinates);
+        }
+        Ok(RationalPoint::Point(parse_complex(x)?, parse_complex(y)?))
+    }
+}
+
+impl<T: Zero + Identity + Mul<Output = T> + MulAssign + AddAssign + Copy + Eq> RationalPoint<T> {
+    /// Check whether the point satisfies ```y^2 = x^3 + ax + b```.
+    /// The point at infinity ```O``` is regarded as lying on every elliptic curve.
+    pub fn is_on_curve(&self, a: T, b: T) -> bool {
+        match *self {
+            RationalPoint::O => true,
+            RationalPoint::Point(x, y) => {
+                let f = Polynomial::new(&[b, a, T::zero(), T::identity()]);
+                y * y == f.eval(x)
+            }
+        }
+    }
+}
+
+/// Implementation of ```ConstantTimeEq``` defined in ```ct_eq.rs```.
+/// Only whether the points are ```O``` is compared by a branch, and both coordinates are always compared otherwise.
+impl<T: ConstantTimeEq> ConstantTimeEq for RationalPoint<T> {
+    fn ct_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RationalPoint::O, RationalPoint::O) => true,
+            (RationalPoint::Point(x1, y1), RationalPoint::Point(x2, y2)) => {
+                x1.ct_eq(x2) & y1.ct_eq(y2)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Implementation of ```ConditionallySelectable``` defined in ```ct_eq.rs```.
+///
+/// A point is stored as a flag, which is ```1``` for ```O```, and the coordinates, which are ```(0, 0)``` for ```O```.
+/// The flag is selected by ```ct_select_u64``` and the coordinates by ```T::conditional_select```,
+/// and the result is rebuilt by indexing an array with the flag, so that nothing branches on ```choice```.
+///
+/// This rebuild is an inherent limit of the enum: its variant is chosen by a memory access depending on the selected flag.
+/// Hence the constant-time ladder ```multiply_ct``` selects ```ProjectivePoint```s instead,
+/// whose coordinates represent ```O``` as well and are all selected by masks.
+impl<T: ConditionallySelectable + Zero + Copy> ConditionallySelectable for RationalPoint<T> {
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        let split = |p: &Self| match *p {
+            RationalPoint::Point(x, y) => (0, x, y),
+            RationalPoint::O => (1, T::zero(), T::zero()),
+        };
+        let (flag1, x1, y1) = split(a);
+        let (flag2, x2, y2) = split(b);
+        let flag = ct_select_u64(flag1, flag2, choice);
+        let x = T::conditional_select(&x1, &x2, choice);
+        let y = T::conditional_select(&y1, &y2, choice);
+        [RationalPoint::Point(x, y), RationalPoint::O][flag as usize]
+    }
+}
+
+/// Implementation of ```Neg```.
+impl<T: Neg<Output = T>> Neg for RationalPoint<T> {
+    type Output = Self;
+    /// Overloading the operator ```-```, i.e. the inverse element ```-(x, y) = (x, -y)``` on an elliptic curve.
+    fn neg(self) -> Self {
+        match self {
+            RationalPoint::O => RationalPoint::O,
+            RationalPoint::Point(x, y) => RationalPoint::Point(x, -y),
+        }
+    }
+}
+
+impl<T: Field> RationalPoint<T> {
     /// An addition of rational points on an elliptic curve.
+    ///
+    /// This function panics if an inversion fails, see ```try_add```.
     pub fn add_rational_points(&self, rhs: &Self, a: T) -> Self {
+        self.try_add(rhs, a).unwrap()
+    }
+
+    /// An addition of rational points on an elliptic curve.
+    /// It returns ```Err(CurveError::NotInvertible)``` instead of panicking if an inversion fails,
+    /// which may happen e.g. when the coordinates do not lie in a field.
+    pub fn try_add(&self, rhs: &Self, a: T) -> Result<Self, CurveError> {
         match *self {
-            RationalPoint::O => *rhs,
+            RationalPoint::O => Ok(*rhs),
             RationalPoint::Point(x1, y1) => match *rhs {
-                RationalPoint::O => RationalPoint::Point(x1, y1),
+                RationalPoint::O => Ok(RationalPoint::Point(x1, y1)),
                 RationalPoint::Point(x2, y2) => {
                     if x1 == x2 {
                         if y1 == -y2 {
-                            RationalPoint::O
+                            Ok(RationalPoint::O)
                         } else {
-                            let id = T::identity();
-                            let m = ((id + id + id) * x1 * x1 + a)
-                                * ((id + id) * y1).inverse().unwrap();
-                            RationalPoint::Point(m * m - x1 - x1, m * (x1 - m * m + x1 + x1) - y1)
+                            self.try_double(a)
                         }
                     } else {
-                        let m = (y2 - y1) * ((x2 - x1).inverse().unwrap());
-                        RationalPoint::Point(m * m - x1 - x2, m * (x1 - m * m + x1 + x2) - y1)
+                        let inv = (x2 - x1).inverse().ok_or(CurveError::NotInvertible)?;
+                        let m = (y2 - y1) * inv;
+                        Ok(RationalPoint::Point(
+                            m * m - x1 - x2,
+                            m * (x1 - m * m + x1 + x2) - y1,
+                        ))
                     }
                 }
             },
         }
     }
+
+    /// A doubling of a rational point on an elliptic curve, i.e. ```self + self```.
+    ///
+    /// This function panics if an inversion fails, see ```try_double```.
+    pub fn double(&self, a: T) -> Self {
+        self.try_double(a).unwrap()
+    }
+
+    /// A doubling of a rational point on an elliptic curve.
+    /// It returns ```Err(CurveError::NotInvertible)``` instead of panicking if an inversion fails.
+    pub fn try_double(&self, a: T) -> Result<Self, CurveError> {
+        match *self {
+            RationalPoint::O => Ok(RationalPoint::O),
+            RationalPoint::Point(x, y) => {
+                if y == -y {
+                    Ok(RationalPoint::O)
+                } else {
+                    let id = T::identity();
+                    let inv = ((id + id) * y).inverse().ok_or(CurveError::NotInvertible)?;
+                    let m = ((id + id + id) * x * x + a) * inv;
+                    Ok(RationalPoint::Point(
+                        m * m - x - x,
+                        m * (x - m * m + x + x) - y,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// An addition of rational points on an elliptic curve in the general Weierstrass form
+    /// ```y^2 + a1 xy + a3 y = x^3 + a2 x^2 + a4 x + a6```, which is needed e.g. in characteristic 2 or 3.
+    ///
+    /// For ```a1 = a2 = a3 = 0```, it coincides with ```add_rational_points``` with ```a = a4```.
+    /// In debug builds, it panics unless both of the points lie on the curve. It panics also if an inversion fails.
+    pub fn add_general(&self, rhs: &Self, a1: T, a2: T, a3: T, a4: T, a6: T) -> Self {
+        let on_curve = |p: &Self| match *p {
+            RationalPoint::O => true,
+            RationalPoint::Point(x, y) => {
+                y * y + a1 * x * y + a3 * y == x * x * x + a2 * x * x + a4 * x + a6
+            }
+        };
+        debug_assert!(on_curve(self), "the left operand is not on the curve");
+        debug_assert!(on_curve(rhs), "the right operand is not on the curve");
+        let (x1, y1, x2, y2) = match (*self, *rhs) {
+            (RationalPoint::O, _) => return *rhs,
+            (_, RationalPoint::O) => return *self,
+            (RationalPoint::Point(x1, y1), RationalPoint::Point(x2, y2)) => (x1, y1, x2, y2),
+        };
+        // The inverse of (x, y) is (x, -y - a1 x - a3).
+        if x1 == x2 && y1 + y2 + a1 * x2 + a3 == T::zero() {
+            return RationalPoint::O;
+        }
+        let id = T::identity();
+        let lambda = if x1 == x2 {
+            let numerator = (id + id + id) * x1 * x1 + (id + id) * a2 * x1 + a4 - a1 * y1;
+            numerator * ((id + id) * y1 + a1 * x1 + a3).inverse().unwrap()
+        } else {
+            (y2 - y1) * (x2 - x1).inverse().unwrap()
+        };
+        let nu = y1 - lambda * x1;
+        let x3 = lambda * lambda + a1 * lambda - a2 - x1 - x2;
+        RationalPoint::Point(x3, -(lambda + a1) * x3 - nu - a3)
+    }
+
+    /// A subtraction of rational points on an elliptic curve, i.e. ```self + (-rhs)```.
+    pub fn sub_rational_points(&self, rhs: &Self, a: T) -> Self {
+        self.add_rational_points(&-*rhs, a)
+    }
+
+    /// Calculate nP using the non-adjacent form of n.
+    ///
+    /// Since about 1/3 of the digits of the non-adjacent form are non-zero,
+    /// this needs fewer additions than the binary method, where about 1/2 of the digits are non-zero.
+    pub fn multiply_naf(&self, a: T, n: u64) -> Self {
+        let mut res = RationalPoint::O;
+        for &d in naf(n).iter().rev() {
+            res = res.double(a);
+            if d == 1 {
+                res = res.add_rational_points(self, a);
+            } else if d == -1 {
+                res = res.sub_rational_points(self, a);
+            }
+        }
+        res
+    }
+
+    /// Calculate nP by repeated squaring
+    /// where n is a positive integer and P is a rational point on an elliptic curve.
+    ///
+    /// The computation is done in projective coordinates,
+    /// so that only one inversion is needed to make the result affine.
+    pub fn multiply_rational_point(&self, a: T, n: u64) -> Self {
+        match *self {
+            RationalPoint::O => RationalPoint::O,
+            RationalPoint::Point(_, _) => ProjectivePoint::from_affine(self)
+                .multiply(a, n)
+                .to_affine(),
+        }
+    }
+
+    /// Calculate nP by repeated squaring in affine coordinates, which needs an inversion for every addition and doubling.
+    ///
+    /// This is the method which ```multiply_rational_point``` replaces, kept as a reference for it.
+    pub fn multiply_affine(&self, a: T, n: u64) -> Self {
+        let mut res = RationalPoint::O;
+        let mut now = *self;
+        let mut m = n;
+        while m > 0 {
+            if m % 2 == 1 {
+                res = res.add_rational_points(&now, a);
+            }
+            now = now.double(a);
+            m /= 2;
+        }
+        res
+    }
+
+    /// The same as ```multiply_rational_point```, but ```n``` is reduced by ```reduce_exponent``` first,
+    /// where ```order``` is a multiple of the order of the point, e.g. the order of the group.
+    pub fn multiply_with_order(&self, a: T, n: u64, order: u64) -> Self {
+        self.multiply_rational_point(a, reduce_exponent(n, order))
+    }
+
+    /// The same as ```multiply_rational_point```, but the scalar must satisfy ```0 < n < order```,
+    /// where ```order``` is the order of the point.
+    ///
+    /// Since ```nP``` depends only on ```n mod order```, larger scalars are silently reduced,
+    /// which would hide a secret key chosen out of ```[1, order)``` by a bug,
+    /// and ```n = 0``` gives the point at infinity ```O```, i.e. a shared secret known to everyone.
+    pub fn multiply_checked(&self, a: T, n: u64, order: u64) -> Result<Self, ScalarError> {
+        if n == 0 {
+            Err(ScalarError::Zero)
+        } else if n >= order {
+            Err(ScalarError::OutOfRange)
+        } else {
+            Ok(self.multiply_rational_point(a, n))
+        }
+    }
+
+    /// The table ```[0P, 1P, ..., (2^window - 1)P]``` for ```multiply_windowed```.
+    ///
+    /// It is worth computing once for a point multiplied many times, e.g. the base point of ECDH.
+    pub fn precompute(&self, a: T, window: usize) -> Vec<Self> {
+        assert!(
+            (1..=16).contains(&window),
+            "window must satisfy 1 <= window <= 16"
+        );
+        let mut table = vec![RationalPoint::O];
+        for i in 1..1 << window {
+            table.push(table[i - 1].add_rational_points(self, a));
+        }
+        table
+    }
+
+    /// Calculate nP by the fixed-window method, where ```table``` is ```self.precompute(a, window)```.
+    ///
+    /// The ```window```-bit digits of ```n``` are processed from the top,
+    /// so that about 64 doublings but only about ```64 / window``` additions are needed,
+    /// whereas the binary method needs up to 64 additions.
+    pub fn multiply_windowed(&self, table: &[Self], a: T, n: u64) -> Self {
+        assert!(
+            table.len().is_power_of_two() && table.len() > 1,
+            "table must be made by precompute"
+        );
+        let window = table.len().trailing_zeros();
+        let mask = (1u64 << window) - 1;
+        let mut res = ProjectivePoint::infinity();
+        let mut shift = 64u32.div_ceil(window) * window;
+        while shift > 0 {
+            shift -= window;
+            for _ in 0..window {
+                res = res.double(a);
+            }
+            let digit = (n.checked_shr(shift).unwrap_or(0) & mask) as usize;
+            res = res.add(&ProjectivePoint::from_affine(&table[digit]), a);
+        }
+        res.to_affine()
+    }
+
+    /// Calculate ```k_1 P_1 + ... + k_m P_m``` for ```terms = [(k_1, P_1), ..., (k_m, P_m)]```.
+    ///
+    /// The doublings are shared by all the terms (Straus' method, also known as Shamir's trick),
+    /// so that it needs 64 doublings in total instead of 64 doublings for each term.
+    pub fn multi_scalar_mul(terms: &[(u64, Self)], a: T) -> Self {
+        let points: Vec<ProjectivePoint<T>> = terms
+            .iter()
+            .map(|(_, p)| ProjectivePoint::from_affine(p))
+            .collect();
+        let mut res = ProjectivePoint::infinity();
+        for i in (0..64).rev() {
+            res = res.double(a);
+            for (&(k, _), p) in terms.iter().zip(points.iter()) {
+                if (k >> i) & 1 == 1 {
+                    res = res.add(p, a);
+                }
+            }
+        }
+        res.to_affine()
+    }
+
+    /// Map the point into the subgroup of prime order ```r```, i.e. multiply it by ```cofactor```,
+    /// where ```cofactor``` is ```elliptic_curve::cofactor(group_order, r)```.
+    pub fn clear_cofactor(&self, a: T, cofactor: u64) -> Self {
+        self.multiply_rational_point(a, cofactor)
+    }
+
+    /// The multiples ```P, 2P, 3P, ...``` of the point, ending with the first one equal to ```O```.
+    ///
+    /// Hence the number of the items is the order of the point, and they form the cyclic subgroup generated by it.
+    pub fn multiples(&self, a: T) -> impl Iterator<Item = Self> {
+        let g = *self;
+        core::iter::successors(Some(g), move |q| {
+            if q.is_infinity() {
+                None
+            } else {
+                Some(q.add_rational_points(&g, a))
+            }
+        })
+    }
+
+    /// The order of the point computed by adding it until ```O``` appears, which takes ```O(order)``` steps.
+    /// It returns ```None``` if the order is greater than ```upper```.
+    ///
+    /// This is the straightforward method which ```order``` replaces, kept as a reference for it.
+    pub fn order_naive(&self, a: T, upper: u64) -> Option<u64> {
+        let mut now = *self;
+        for n in 1..=upper {
+            if now == RationalPoint::O {
+                return Some(n);
+            }
+            now = now.add_rational_points(self, a);
+        }
+        None
+    }
+}
+
+impl<T: Field + ConditionallySelectable> RationalPoint<T> {
+    /// Calculate nP by the Montgomery ladder in projective coordinates, see ```ProjectivePoint::multiply_ct```.
+    ///
+    /// This should be used when ```n``` is secret, e.g. in ECDH,
+    /// since the time taken by ```multiply_rational_point``` depends on the bits of ```n```.
+    pub fn multiply_ct(&self, a: T, n: u64) -> Self {
+        match *self {
+            RationalPoint::O => RationalPoint::O,
+            RationalPoint::Point(_, _) => ProjectivePoint::from_affine(self)
+                .multiply_ct(a, n)
+                .to_affine(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Field + Hash> RationalPoint<T> {
+    /// The order of a rational point on an elliptic curve, i.e. the smallest ```n > 0``` with ```nP = O```,
+    /// computed by the baby-step giant-step algorithm in ```O(√upper)``` steps.
+    /// It returns ```None``` if the order is greater than ```upper```.
+    pub fn order(&self, a: T, upper: u64) -> Option<u64> {
+        if *self == RationalPoint::O {
+            return Some(1);
+        }
+        let m = isqrt(upper) + 1;
+        let mut baby_steps: HashMap<Self, u64> = HashMap::new();
+        let mut now = RationalPoint::O;
+        for j in 0..m {
+            if j > 0 && now == RationalPoint::O {
+                return if j <= upper { Some(j) } else { None };
+            }
+            baby_steps.insert(now, j);
+            now = now.add_rational_points(self, a);
+        }
+        let giant_step = now;
+        for i in 1..=m {
+            if let Some(&j) = baby_steps.get(&now) {
+                let n = i * m - j;
+                return if n <= upper { Some(n) } else { None };
+            }
+            now = now.add_rational_points(&giant_step, a);
+        }
+        None
+    }
+
+    /// The discrete logarithm of ```q``` to the base ```self```, i.e. the ```k``` in ```[0, order)``` with ```kP = q```,
+    /// computed by the baby-step giant-step algorithm in ```O(√order)``` steps,
+    /// where ```order``` is the order of ```P``` (e.g. computed by ```order```).
+    /// It returns ```None``` if ```q``` is not a multiple of ```P```.
+    pub fn discrete_log(&self, q: &Self, a: T, order: u64) -> Option<u64> {
+        let m = isqrt(order) + 1;
+        let mut baby_steps: HashMap<Self, u64> = HashMap::new();
+        let mut now = RationalPoint::O;
+        for j in 0..m {
+            baby_steps.entry(now).or_insert(j);
+            now = now.add_rational_points(self, a);
+        }
+        // now = mP, and q - i(mP) is searched among the baby steps.
+        let giant_step = -now;
+        let mut gamma = *q;
+        for i in 0..m {
+            if let Some(&j) = baby_steps.get(&gamma) {
+                let k = i * m + j;
+                return if k < order { Some(k) } else { None };
+            }
+            gamma = gamma.add_rational_points(&giant_step, a);
+        }
+        None
+    }
 }
 
 impl<
-        T: Add<Output = T>
+        T: PrimeField
+            + Copy
+            + Add<Output = T>
             + Mul<Output = T>
             + Sub<Output = T>
-            + Copy
+            + Neg<Output = T>
             + Eq
-            + Inverse
-            + Identity
-            + Neg<Output = T>,
-    > RationalPoint<T>
+            + Zero
+            + Identity,
+    > RationalPoint<Complex<T>>
 {
-    /// Calculate nP by repeated squaring
-    /// where n is a positive integer and P is a rational point on an elliptic curve.
-    pub fn multiply_rational_point(&self, a: T, n: u64) -> Self {
-        match *self {
-            RationalPoint::O => RationalPoint::O,
-            RationalPoint::Point(_, _) => {
-                let mut res = RationalPoint::O;
-                let mut now = *self;
-                let mut m = n;
-                loop {
-                    if m == 0 {
-                        break;
-                    }
-                    if m % 2 == 1 {
-                        res = res.add_rational_points(&now, a);
-                    }
-                    now = now.add_rational_points(&now, a);
-                    m = m / 2;
+    /// The rational point on the elliptic curve ```y^2 = x^3 + ax + b``` over ```F_(p^2)``` with the x-coordinate ```x```,
+    /// where the y-coordinate is the square root of ```x^3 + ax + b``` whose parity (see ```Complex::is_odd```) is ```choose_odd```.
+    ///
+    /// It returns ```None``` if ```x``` is not an x-coordinate of any point on the curve,
+    /// or if ```choose_odd``` is ```true``` while ```y = 0```.
+    pub fn from_x(x: Complex<T>, a: Complex<T>, b: Complex<T>, choose_odd: bool) -> Option<Self> {
+        let y = (x * x * x + a * x + b).sqrt()?;
+        if y.is_odd() == choose_odd {
+            Some(RationalPoint::Point(x, y))
+        } else if y == Complex::zero() {
+            None
+        } else {
+            Some(RationalPoint::Point(x, -y))
+        }
+    }
+
+    /// A random rational point on the elliptic curve ```y^2 = x^3 + ax + b``` over ```F_(p^2)```.
+    ///
+    /// An x-coordinate and a parity are sampled until ```from_x``` finds a point with them.
+    pub fn random_point<R: Rng>(a: Complex<T>, b: Complex<T>, rng: &mut R) -> Self {
+        let p = T::characteristic();
+        loop {
+            let x = Complex::new(
+                T::from_int(rng.gen_range(0, p)),
+                T::from_int(rng.gen_range(0, p)),
+            );
+            if let Some(point) = Self::from_x(x, a, b, rng.gen()) {
+                return point;
+            }
+        }
+    }
+
+    /// Point compression. It returns the x-coordinate and the parity of the y-coordinate (see ```Complex::is_odd```),
+    /// or ```None``` for the point at infinity, which has no affine coordinates.
+    pub fn compress(&self) -> Option<(Complex<T>, bool)> {
+        let (x, y) = self.affine()?;
+        Some((x, y.is_odd()))
+    }
+
+    /// Point decompression, i.e. the inverse of ```compress``` for the curve ```y^2 = x^3 + ax + b```.
+    ///
+    /// It returns ```None``` if ```x``` is not an x-coordinate of any point on the curve,
+    /// or if ```sign``` is ```true``` while ```y = 0```.
+    pub fn decompress(x: Complex<T>, sign: bool, a: Complex<T>, b: Complex<T>) -> Option<Self> {
+        Self::from_x(x, a, b, sign)
+    }
+
+    /// Find a generator of the largest prime-order subgroup of ```E(F_(p^2))```,
+    /// where ```group_order``` is the order of the whole group (e.g. computed by ```count_points```).
+    ///
+    /// A random point is multiplied by the cofactor ```group_order / r``` for the largest prime factor ```r```,
+    /// and the result is verified to have the order ```r```.
+    /// It returns the generator and its order, or ```None``` if the verification fails, i.e. ```group_order``` is wrong.
+    /// It also gives up with ```None``` if ```GENERATOR_ATTEMPTS``` random points are all multiplied to ```O```,
+    /// which happens with probability at most ```2^-64``` for the right ```group_order```.
+    pub fn find_generator<R: Rng>(
+        a: Complex<T>,
+        b: Complex<T>,
+        group_order: u64,
+        rng: &mut R,
+    ) -> Option<(Self, u64)> {
+        const GENERATOR_ATTEMPTS: usize = 64;
+        let &(r, _) = factorize(group_order).last()?;
+        let h = cofactor(group_order, r);
+        for _ in 0..GENERATOR_ATTEMPTS {
+            let g = Self::random_point(a, b, rng).clear_cofactor(a, h);
+            if g != RationalPoint::O {
+                return if g.multiply_rational_point(a, r) == RationalPoint::O {
+                    Some((g, r))
+                } else {
+                    None
+                };
+            }
+        }
+        None
+    }
+}
+
+/// An accumulator summing rational points on ```y^2 = x^3 + ax + b```,
+/// which keeps the coefficient ```a``` so that it is not passed for every addition.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PointSum<T> {
+    a: T,
+    sum: RationalPoint<T>,
+}
+
+impl<T: Field> PointSum<T> {
+    /// Constructor of the empty sum ```O``` on the curve with the coefficient ```a```.
+    pub fn new(a: T) -> Self {
+        Self {
+            a,
+            sum: RationalPoint::O,
+        }
+    }
+
+    /// Add a point to the accumulator by ```add_rational_points```.
+    pub fn add(&mut self, p: &RationalPoint<T>) {
+        self.sum = self.sum.add_rational_points(p, self.a);
+    }
+
+    /// The sum of the points added so far.
+    pub fn finish(self) -> RationalPoint<T> {
+        self.sum
+    }
+}
+
+/// A rational point on ```y^2 = x^3 + ax + b``` together with its order,
+/// which is computed by ```RationalPoint::order``` on the first request and reused afterwards.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct OrderedPoint<T> {
+    point: RationalPoint<T>,
+    a: T,
+    upper: u64,
+    order: Cell<Option<Option<u64>>>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Field + Hash> OrderedPoint<T> {
+    /// Constructor, where ```upper``` is passed to ```RationalPoint::order```, e.g. the upper end of ```hasse_interval```.
+    /// The order is not computed yet.
+    pub fn new(point: RationalPoint<T>, a: T, upper: u64) -> Self {
+        Self {
+            point,
+            a,
+            upper,
+            order: Cell::new(None),
+        }
+    }
+
+    /// The point.
+    pub fn point(&self) -> RationalPoint<T> {
+        self.point
+    }
+
+    /// The order of the point, or ```None``` if it is greater than ```upper```.
+    /// Only the first call computes it.
+    pub fn order(&self) -> Option<u64> {
+        if let Some(order) = self.order.get() {
+            return order;
+        }
+        let order = self.point.order(self.a, self.upper);
+        self.order.set(Some(order));
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complexification::Complex;
+    use crate::modint::ModInt;
+    use alloc::string::ToString;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    type F = ModInt<863>;
+    type C = Complex<F>;
+
+    fn c(re: u64, im: u64) -> C {
+        Complex::new(F::new(re), F::new(im))
+    }
+
+    /// The coefficients of the curve ```y^2 = x^3 + 2x + 3``` over ```F_(863^2)```.
+    fn curve() -> (C, C) {
+        (c(2, 0), c(3, 0))
+    }
+
+    fn random_points(n: usize, seed: u64) -> Vec<RationalPoint<C>> {
+        let (a, b) = curve();
+        let mut rng = SmallRng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| RationalPoint::random_point(a, b, &mut rng))
+            .collect()
+    }
+
+    #[test]
+    fn point_plus_negation_is_infinity() {
+        let (a, _) = curve();
+        for p in random_points(100, 0) {
+            assert_eq!(p.add_rational_points(&-p, a), RationalPoint::O);
+        }
+        assert_eq!(-RationalPoint::<C>::O, RationalPoint::O);
+    }
+
+    #[test]
+    fn sum_minus_summand_is_original_point() {
+        let (a, _) = curve();
+        let points = random_points(100, 1);
+        for pair in points.chunks(2) {
+            let (p, q) = (pair[0], pair[1]);
+            assert_eq!(p.add_rational_points(&q, a).sub_rational_points(&q, a), p);
+        }
+    }
+
+    #[test]
+    fn tampered_point_is_not_on_curve() {
+        let (a, b) = curve();
+        for p in random_points(100, 2) {
+            assert!(p.is_on_curve(a, b));
+            if let RationalPoint::Point(x, y) = p {
+                assert!(!RationalPoint::Point(x, y + c(1, 0)).is_on_curve(a, b));
+            }
+        }
+        assert!(RationalPoint::<C>::O.is_on_curve(a, b));
+    }
+
+    #[test]
+    fn double_matches_addition_to_itself() {
+        let (a, _) = curve();
+        for p in random_points(100, 3) {
+            assert_eq!(p.double(a), p.add_rational_points(&p, a));
+        }
+        assert_eq!(RationalPoint::<C>::O.double(a), RationalPoint::O);
+        assert_eq!(
+            RationalPoint::Point(c(5, 0), C::zero()).double(a),
+            RationalPoint::O
+        );
+    }
+
+    /// All the points of ```y^2 = x^3 + 2x + 3``` over ```F_863``` itself, found by brute force.
+    fn base_field_points() -> Vec<RationalPoint<F>> {
+        let (a, b) = (F::new(2), F::new(3));
+        let mut points = vec![RationalPoint::O];
+        for x in (0..863).map(F::new) {
+            for y in (0..863).map(F::new) {
+                if y * y == x * x * x + a * x + b {
+                    points.push(RationalPoint::Point(x, y));
                 }
-                res
+            }
+        }
+        points
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn order_matches_naive_order() {
+        let a = F::new(2);
+        let points = base_field_points();
+        // The Hasse bound ```#E(F_863) <= 863 + 1 + 2√863```.
+        let upper = 863 + 1 + 2 * (crate::util::isqrt(863) + 1);
+        for p in &points {
+            let order = p.order(a, upper);
+            assert!(order.is_some());
+            assert_eq!(order, p.order_naive(a, upper));
+            assert_eq!(
+                p.multiply_rational_point(a, order.unwrap()),
+                RationalPoint::O
+            );
+        }
+        let p = points[1];
+        let order = p.order(a, upper).unwrap();
+        assert_eq!(p.order(a, order - 1), None);
+        assert_eq!(p.order_naive(a, order - 1), None);
+    }
+
+    #[test]
+    fn random_points_lie_on_curve() {
+        let (a, b) = curve();
+        for p in random_points(100, 4) {
+            assert!(p.is_on_curve(a, b));
+            assert_ne!(p, RationalPoint::O);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generator_has_largest_prime_order() {
+        let (a, b) = curve();
+        let group_order = crate::elliptic_curve::EllipticCurve::new(a, b).count_points();
+        let &(r, _) = factorize(group_order).last().unwrap();
+        let mut rng = SmallRng::seed_from_u64(5);
+        let (g, order) = RationalPoint::find_generator(a, b, group_order, &mut rng).unwrap();
+        assert_eq!(order, r);
+        assert!(g.is_on_curve(a, b));
+        assert_eq!(g.order(a, group_order), Some(r));
+        assert_eq!(
+            RationalPoint::find_generator(a, b, group_order + 2, &mut rng),
+            None
+        );
+        // The cofactor is then a multiple of the true order, so that every point is multiplied to O.
+        assert_eq!(
+            RationalPoint::find_generator(a, b, group_order * 1_000_003, &mut rng),
+            None
+        );
+    }
+
+    #[test]
+    fn projective_multiplication_matches_affine() {
+        let (a, _) = curve();
+        let mut rng = SmallRng::seed_from_u64(6);
+        for p in random_points(50, 6) {
+            let n: u64 = rng.gen();
+            assert_eq!(p.multiply_rational_point(a, n), p.multiply_affine(a, n));
+            assert_eq!(p.multiply_rational_point(a, 0), RationalPoint::O);
+        }
+        assert_eq!(
+            RationalPoint::<C>::O.multiply_rational_point(a, 5),
+            RationalPoint::O
+        );
+    }
+
+    #[test]
+    fn naf_multiplication_matches_binary() {
+        let (a, _) = curve();
+        let mut rng = SmallRng::seed_from_u64(7);
+        for p in random_points(50, 7) {
+            let n: u64 = rng.gen();
+            assert_eq!(p.multiply_naf(a, n), p.multiply_affine(a, n));
+            assert_eq!(p.multiply_naf(a, u64::MAX), p.multiply_affine(a, u64::MAX));
+        }
+        let p = random_points(1, 8)[0];
+        for n in 0..100 {
+            assert_eq!(p.multiply_naf(a, n), p.multiply_affine(a, n));
+        }
+    }
+
+    #[test]
+    fn compression_round_trips() {
+        let (a, b) = curve();
+        for p in random_points(100, 9) {
+            let (x, sign) = p.compress().unwrap();
+            assert_eq!(RationalPoint::decompress(x, sign, a, b), Some(p));
+            assert_eq!(RationalPoint::decompress(x, !sign, a, b), Some(-p));
+        }
+        assert_eq!(RationalPoint::<C>::O.compress(), None);
+    }
+
+    #[test]
+    fn degenerate_inputs_give_errors() {
+        // Over Z/15Z, which is not a field, x2 - x1 = 3 and 2y = 6 are not invertible.
+        type Z15 = ModInt<15>;
+        let a = Z15::new(1);
+        let p = RationalPoint::Point(Z15::new(1), Z15::new(3));
+        let q = RationalPoint::Point(Z15::new(4), Z15::new(2));
+        assert_eq!(p.try_add(&q, a), Err(CurveError::NotInvertible));
+        assert_eq!(p.try_double(a), Err(CurveError::NotInvertible));
+        assert_eq!(p.try_add(&p, a), Err(CurveError::NotInvertible));
+        assert_eq!(p.try_add(&RationalPoint::O, a), Ok(p));
+        let (a, _) = curve();
+        let r = random_points(1, 10)[0];
+        assert_eq!(r.try_add(&r, a), Ok(r.double(a)));
+    }
+
+    #[test]
+    fn constant_time_multiplication_matches_variable_time_one() {
+        let (a, _) = curve();
+        let mut rng = SmallRng::seed_from_u64(11);
+        for p in random_points(10, 12) {
+            for _ in 0..20 {
+                let n: u64 = rng.gen();
+                assert_eq!(p.multiply_ct(a, n), p.multiply_rational_point(a, n));
+            }
+        }
+    }
+
+    #[test]
+    fn points_satisfy_group_laws() {
+        let (a, _) = curve();
+        let mut points = random_points(60, 13);
+        points.push(RationalPoint::O);
+        for p in points.iter() {
+            assert_eq!(p.add_rational_points(&RationalPoint::O, a), *p);
+            assert_eq!(RationalPoint::O.add_rational_points(p, a), *p);
+            assert_eq!(p.add_rational_points(&-*p, a), RationalPoint::O);
+            for q in points.iter() {
+                let pq = p.add_rational_points(q, a);
+                assert_eq!(pq, q.add_rational_points(p, a));
+                for r in points.iter().step_by(7) {
+                    assert_eq!(
+                        pq.add_rational_points(r, a),
+                        p.add_rational_points(&q.add_rational_points(r, a), a)
+                    );
+                }
+            }
+        }
+
+        let points = base_field_points();
+        let a = F::new(2);
+        let mut rng = SmallRng::seed_from_u64(14);
+        for _ in 0..2000 {
+            let p = points[rng.gen_range(0, points.len())];
+            let q = points[rng.gen_range(0, points.len())];
+            let r = points[rng.gen_range(0, points.len())];
+            assert_eq!(
+                p.add_rational_points(&q, a).add_rational_points(&r, a),
+                p.add_rational_points(&q.add_rational_points(&r, a), a)
+            );
+        }
+    }
+
+    #[test]
+    fn windowed_multiplication_matches_binary() {
+        let (a, _) = curve();
+        let mut rng = SmallRng::seed_from_u64(15);
+        let p = random_points(1, 16)[0];
+        let scalars: Vec<u64> = (0..20)
+            .map(|_| rng.gen())
+            .chain([0, 1, 15, 16, u64::MAX])
+            .collect();
+        for window in 1..=6 {
+            let table = p.precompute(a, window);
+            assert_eq!(table.len(), 1 << window);
+            for &n in scalars.iter() {
+                assert_eq!(
+                    p.multiply_windowed(&table, a, n),
+                    p.multiply_rational_point(a, n)
+                );
+            }
+        }
+        // The additions are one per non-zero 4-bit digit instead of one per bit 1.
+        let digits: u32 = scalars
+            .iter()
+            .map(|&n| (0..16).filter(|i| (n >> (4 * i)) & 15 != 0).count() as u32)
+            .sum();
+        let bits: u32 = scalars.iter().map(|n| n.count_ones()).sum();
+        assert!(digits * 2 < bits);
+    }
+
+    #[test]
+    fn multi_scalar_multiplication_matches_sum_of_multiples() {
+        let points = base_field_points();
+        let a = F::new(2);
+        let mut rng = SmallRng::seed_from_u64(17);
+        for m in 0..6 {
+            let terms: Vec<(u64, RationalPoint<F>)> = (0..m)
+                .map(|_| (rng.gen(), points[rng.gen_range(0, points.len())]))
+                .collect();
+            let expected = terms.iter().fold(RationalPoint::O, |acc, (k, p)| {
+                acc.add_rational_points(&p.multiply_rational_point(a, *k), a)
+            });
+            assert_eq!(RationalPoint::multi_scalar_mul(&terms, a), expected);
+        }
+    }
+
+    #[test]
+    fn point_sum_matches_nested_additions() {
+        let (a, _) = curve();
+        let points = random_points(3, 18);
+        let (p, q, r) = (points[0], points[1], points[2]);
+        let mut sum = PointSum::new(a);
+        for point in points.iter() {
+            sum.add(point);
+        }
+        assert_eq!(
+            sum.finish(),
+            p.add_rational_points(&q, a).add_rational_points(&r, a)
+        );
+        assert_eq!(PointSum::new(a).finish(), RationalPoint::O);
+    }
+
+    #[test]
+    fn infinity_and_affine_accessors() {
+        let p = RationalPoint::Point(c(1, 2), c(3, 4));
+        assert!(!p.is_infinity());
+        assert_eq!(p.affine(), Some((c(1, 2), c(3, 4))));
+        assert!(RationalPoint::<C>::O.is_infinity());
+        assert_eq!(RationalPoint::<C>::O.affine(), None);
+        assert_eq!(RationalPoint::<C>::default(), RationalPoint::O);
+    }
+
+    #[test]
+    fn general_addition_matches_short_form() {
+        let (a, b) = curve();
+        let zero = C::zero();
+        let points = random_points(60, 30);
+        for (i, p) in points.iter().enumerate() {
+            for q in points.iter().skip(i) {
+                assert_eq!(
+                    p.add_general(q, zero, zero, zero, a, b),
+                    p.add_rational_points(q, a)
+                );
+            }
+            assert_eq!(
+                p.add_general(&-*p, zero, zero, zero, a, b),
+                RationalPoint::O
+            );
+            assert_eq!(p.add_general(p, zero, zero, zero, a, b), p.double(a));
+        }
+    }
+
+    #[test]
+    fn general_addition_stays_on_the_curve() {
+        type G = ModInt<13>;
+        let (a1, a2, a3, a4, a6) = (G::new(1), G::new(2), G::new(3), G::new(4), G::new(5));
+        let on_curve = |p: &RationalPoint<G>| match *p {
+            RationalPoint::O => true,
+            RationalPoint::Point(x, y) => {
+                y * y + a1 * x * y + a3 * y == x * x * x + a2 * x * x + a4 * x + a6
+            }
+        };
+        let mut points = vec![RationalPoint::O];
+        for x in crate::modint::all::<13>() {
+            for y in crate::modint::all::<13>() {
+                if on_curve(&RationalPoint::Point(x, y)) {
+                    points.push(RationalPoint::Point(x, y));
+                }
+            }
+        }
+        assert!(points.len() > 1);
+        for p in &points {
+            for q in &points {
+                let sum = p.add_general(q, a1, a2, a3, a4, a6);
+                assert!(on_curve(&sum));
+                assert_eq!(sum, q.add_general(p, a1, a2, a3, a4, a6));
+            }
+        }
+    }
+
+    #[test]
+    fn default_is_infinity() {
+        assert_eq!(RationalPoint::<C>::default(), RationalPoint::O);
+        let mut p = random_points(1, 40)[0];
+        let q = p;
+        assert_eq!(core::mem::take(&mut p), q);
+        assert_eq!(p, RationalPoint::O);
+    }
+
+    #[test]
+    fn parities_of_from_x_give_negated_points() {
+        let (a, b) = curve();
+        let mut found = 0;
+        for n in 0..200 {
+            let x = c(n, 1);
+            match (
+                RationalPoint::from_x(x, a, b, false),
+                RationalPoint::from_x(x, a, b, true),
+            ) {
+                (Some(even), Some(odd)) => {
+                    found += 1;
+                    assert_eq!(odd, -even);
+                    assert!(even.is_on_curve(a, b));
+                    let (_, y) = even.affine().unwrap();
+                    assert!(!y.is_odd());
+                    assert!(odd.affine().unwrap().1.is_odd());
+                }
+                (None, None) => assert!((x * x * x + a * x + b).sqrt().is_none()),
+                _ => panic!("only one of the parities is found for x = {}", x),
+            }
+        }
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn checked_multiplication_rejects_out_of_range_scalars() {
+        let a = F::new(2);
+        let p = base_field_points()[1];
+        let order = p.order_naive(a, 2000).unwrap();
+        assert_eq!(p.multiply_checked(a, 0, order), Err(ScalarError::Zero));
+        assert_eq!(
+            p.multiply_checked(a, order, order),
+            Err(ScalarError::OutOfRange)
+        );
+        assert_eq!(
+            p.multiply_checked(a, u64::MAX, order),
+            Err(ScalarError::OutOfRange)
+        );
+        for n in [1, 2, order / 2, order - 1].iter().copied() {
+            assert_eq!(
+                p.multiply_checked(a, n, order),
+                Ok(p.multiply_rational_point(a, n))
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ordered_point_computes_the_order_only_once() {
+        let (a, _) = curve();
+        let (_, upper) = crate::elliptic_curve::hasse_interval(863 * 863);
+        let p = random_points(1, 50)[0];
+        let ordered = OrderedPoint::new(p, a, upper);
+        assert_eq!(ordered.order.get(), None);
+        let order = ordered.order();
+        assert_eq!(order, p.order(a, upper));
+        assert_eq!(ordered.order.get(), Some(order));
+        // A recomputation would overwrite this wrong value, so it is returned only if the cache is used.
+        ordered.order.set(Some(Some(999)));
+        assert_eq!(ordered.order(), Some(999));
+        assert_eq!(ordered.point(), p);
+    }
+
+    #[test]
+    fn conversion_from_tuples() {
+        assert_eq!(
+            RationalPoint::from((c(1, 2), c(3, 4))),
+            RationalPoint::Point(c(1, 2), c(3, 4))
+        );
+        let p: RationalPoint<C> = (c(5, 0), c(0, 6)).into();
+        assert_eq!(p.affine(), Some((c(5, 0), c(0, 6))));
+    }
+
+    #[test]
+    fn distinct_multiples_count_the_order() {
+        let a = F::new(2);
+        for p in base_field_points().iter().take(20) {
+            let multiples: Vec<RationalPoint<F>> = p.multiples(a).collect();
+            assert_eq!(Some(multiples.len() as u64), p.order_naive(a, 2000));
+            assert_eq!(multiples.last(), Some(&RationalPoint::O));
+            assert_eq!(multiples[0], *p);
+            let mut distinct = multiples.clone();
+            distinct.sort_by_key(|q| q.affine().map(|(x, y)| (x.to_int(), y.to_int())));
+            distinct.dedup();
+            assert_eq!(distinct.len(), multiples.len());
+        }
+    }
+
+    #[test]
+    fn parsing_round_trips_display() {
+        assert_eq!("O".parse::<RationalPoint<C>>(), Ok(RationalPoint::O));
+        assert_eq!(
+            RationalPoint::<C>::O.to_string().parse(),
+            Ok(RationalPoint::<C>::O)
+        );
+        let mut points = random_points(50, 60);
+        points.push(RationalPoint::Point(c(0, 0), c(0, 5)));
+        points.push(RationalPoint::Point(c(7, 0), c(1, 862)));
+        for p in points {
+            assert_eq!(p.to_string().parse(), Ok(p));
+        }
+        assert_eq!(
+            " ( (1 + 2i) ,  3i ) ".parse::<RationalPoint<C>>(),
+            Ok(RationalPoint::Point(c(1, 2), c(0, 3)))
+        );
+    }
+
+    #[test]
+    fn malformed_points_are_rejected() {
+        let parse = |s: &str| s.parse::<RationalPoint<C>>();
+        assert_eq!(parse("o"), Err(ParsePointError::MissingParentheses));
+        assert_eq!(parse("(1, 2"), Err(ParsePointError::MissingParentheses));
+        assert_eq!(parse("(1)"), Err(ParsePointError::WrongNumberOfCoordinates));
+        assert_eq!(
+            parse("(1, 2, 3)"),
+            Err(ParsePointError::WrongNumberOfCoordinates)
+        );
+        assert_eq!(parse("(x, 2)"), Err(ParsePointError::InvalidCoordinate));
+        assert_eq!(
+            parse("((1 - 2i), 2)"),
+            Err(ParsePointError::InvalidCoordinate)
+        );
+        assert_eq!(parse("(863, 2)"), Err(ParsePointError::OutOfRange));
+    }
+
+    #[test]
+    fn conditional_select_returns_the_chosen_operand() {
+        let mut points = random_points(10, 70);
+        points.push(RationalPoint::O);
+        for p in points.iter() {
+            for q in points.iter() {
+                assert_eq!(RationalPoint::conditional_select(p, q, false), *p);
+                assert_eq!(RationalPoint::conditional_select(p, q, true), *q);
             }
         }
     }
@@ -0,0 +1,10 @@
+/// Rings some of whose elements are regarded as negative, e.g. ```p - 1 = -1``` in ```Z / (p)Z```.
+///
+/// This is used only for displaying, e.g. ```x^2 - 1``` instead of ```x^2 + 862``` over ```F_863```.
+/// For rings without such a notion, implement it with the default ```is_negative``` returning ```false```.
+pub trait Signed {
+    /// Whether it should be displayed as ```-(-self)```.
+    fn is_negative(&self) -> bool {
+        false
+    }
+}
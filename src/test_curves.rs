@@ -0,0 +1,32 @@
+//! Curves shared by the tests of several modules.
+
+use crate::complexification::Complex;
+use crate::identities::{Identity, Zero};
+use crate::modint::ModInt;
+use crate::rational_point::RationalPoint;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+/// The field ```F_(43^2)```.
+pub type C = Complex<ModInt<43>>;
+
+/// The coefficients ```(a, b) = (1, 0)``` of the supersingular curve ```y^2 = x^3 + x``` over ```F_(43^2)```,
+/// whose group of rational points is ```(Z / 44Z)^2```, so that its 11-torsion ```E[11]``` is rational.
+pub fn supersingular_curve() -> (C, C) {
+    (C::identity(), C::zero())
+}
+
+/// Random points of ```supersingular_curve```, drawn from ```seed``` so that the tests are deterministic.
+pub fn random_points(seed: u64) -> impl Iterator<Item = RationalPoint<C>> {
+    let (a, b) = supersingular_curve();
+    let mut rng = SmallRng::seed_from_u64(seed);
+    core::iter::repeat_with(move || RationalPoint::random_point(a, b, &mut rng))
+}
+
+/// Random points of order 11 of ```supersingular_curve```, i.e. the random points multiplied by 4 except ```O```.
+pub fn torsion_points(seed: u64) -> impl Iterator<Item = RationalPoint<C>> {
+    let (a, _) = supersingular_curve();
+    random_points(seed)
+        .map(move |p| p.multiply_rational_point(a, 4))
+        .filter(|p| *p != RationalPoint::O)
+}
@@ -0,0 +1,321 @@
+use alloc::vec::Vec;
+use rand::Rng;
+
+/// Integer square root, i.e. the largest ```r``` with ```r^2 <= n```.
+pub fn isqrt(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let bits = 64 - n.leading_zeros();
+    let mut x: u64 = 1 << bits.div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// The greatest common divisor by the Euclidean algorithm. ```gcd(a, 0) = a```, in particular ```gcd(0, 0) = 0```.
+pub fn gcd(a: u64, b: u64) -> u64 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// The least common multiple. ```lcm(a, 0) = 0```.
+/// It panics on overflow in debug builds, i.e. if the result does not fit in ```u64```.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+/// The Chinese remainder theorem, i.e. the ```x``` in ```[0, m_1 m_2 ... m_k)``` with ```x % m_i = r_i``` for every pair ```(r_i, m_i)```.
+/// It returns ```x``` and the product of the moduli,
+/// or ```None``` if the moduli are not positive and pairwise coprime or the product does not fit in ```u64```.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut m) = (0, 1);
+    for &(r, n) in congruences {
+        if n == 0 || gcd(m, n) != 1 {
+            return None;
+        }
+        let product = m.checked_mul(n)?;
+        // x + m * t = r (mod n), where t = (r - x) * m^(-1) (mod n).
+        let m_inv = mod_inverse(m % n, n)?;
+        let diff = (r % n + n - x % n) % n;
+        let t = mul_mod(diff, m_inv, n);
+        x += m * t;
+        m = product;
+    }
+    Some((x, m))
+}
+
+/// The inverse of ```a``` modulo ```m``` by the extended Euclidean algorithm, or ```None``` unless ```gcd(a, m) = 1```.
+pub(crate) fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (m as i128, a as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 != 1 {
+        return None;
+    }
+    Some(t0.rem_euclid(m as i128) as u64)
+}
+
+/// Reduce an exponent (or a scalar) ```n``` modulo ```order```,
+/// which is a multiple of the multiplicative order of an element (or the order of a point), e.g. ```p - 1``` for ```F_p^*```.
+/// Since ```x^order = 1``` (or ```order * P = O```), ```x^n = x^(n mod order)``` (or ```nP = (n mod order)P```).
+///
+/// A positive ```n``` is reduced into ```[1, order]``` rather than ```[0, order)```,
+/// so that ```0^n = 0``` is kept for ```0```, which has no multiplicative order. ```order``` must be positive.
+pub fn reduce_exponent(n: u64, order: u64) -> u64 {
+    assert!(order != 0, "the order must be positive");
+    if n == 0 {
+        0
+    } else {
+        (n - 1) % order + 1
+    }
+}
+
+/// Prime factorization by trial division.
+/// It returns pairs of primes and exponents in ascending order of the primes. For ```n <= 1``` it returns an empty vector.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    let mut m = n;
+    let mut d = 2;
+    while d <= m / d {
+        if m.is_multiple_of(d) {
+            let mut e = 0;
+            while m.is_multiple_of(d) {
+                m /= d;
+                e += 1;
+            }
+            factors.push((d, e));
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    if m > 1 {
+        factors.push((m, 1));
+    }
+    factors
+}
+
+/// The non-adjacent form of ```n```, i.e. the digits ```d_i``` in ```{-1, 0, 1}``` with ```n = Σ d_i 2^i```
+/// such that no two consecutive digits are non-zero. The digits are returned from the least significant one.
+pub fn naf(n: u64) -> Vec<i8> {
+    let mut digits: Vec<i8> = Vec::new();
+    let mut k = n as u128;
+    while k > 0 {
+        if k % 2 == 1 {
+            if k % 4 == 1 {
+                digits.push(1);
+                k -= 1;
+            } else {
+                digits.push(-1);
+                k += 1;
+            }
+        } else {
+            digits.push(0);
+        }
+        k /= 2;
+    }
+    digits
+}
+
+/// ```a * b mod m``` computed in ```u128```, so that it does not overflow.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// ```a^n mod m``` by repeated squaring.
+fn pow_mod(a: u64, n: u64, m: u64) -> u64 {
+    let mut res = 1 % m;
+    let mut a = a % m;
+    let mut n = n;
+    while n > 0 {
+        if n % 2 == 1 {
+            res = mul_mod(res, a, m);
+        }
+        a = mul_mod(a, a, m);
+        n /= 2;
+    }
+    res
+}
+
+/// Primality test by the Miller–Rabin test.
+///
+/// The witnesses are the first 12 primes, which are known to give the correct answer for every ```u64```.
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for &w in &WITNESSES {
+        if n == w {
+            return true;
+        }
+        if n.is_multiple_of(w) {
+            return false;
+        }
+    }
+    // n - 1 = 2^s * d with d odd.
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for &w in &WITNESSES {
+        let mut x = pow_mod(w, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// A random prime ```p``` of exactly ```bits``` bits with ```p % 4 == 3```.
+///
+/// Odd candidates with the top bit set are sampled and forced to be ```3 mod 4```
+/// until one of them passes ```is_prime```.
+/// Since ```DynModInt``` needs moduli less than ```2^63```, ```bits``` must satisfy ```2 <= bits <= 63```.
+pub fn random_prime_3mod4<R: Rng>(bits: u32, rng: &mut R) -> u64 {
+    assert!(
+        (2..=63).contains(&bits),
+        "bits must satisfy 2 <= bits <= 63"
+    );
+    loop {
+        let n = rng.gen_range(1 << (bits - 1), 1 << bits) | 3;
+        if is_prime(n) {
+            return n;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn naf_is_non_adjacent_and_sparser_than_binary() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut scalars: Vec<u64> = (0..1000).map(|_| rng.gen()).collect();
+        scalars.extend_from_slice(&[0, 1, 2, 3, 7, u64::MAX]);
+        for n in scalars {
+            let digits = naf(n);
+            let value: i128 = digits
+                .iter()
+                .enumerate()
+                .map(|(i, &d)| d as i128 * (1i128 << i))
+                .sum();
+            assert_eq!(value, n as i128);
+            assert!(digits.windows(2).all(|w| w[0] == 0 || w[1] == 0));
+            let additions = digits.iter().filter(|&&d| d != 0).count() as u32;
+            assert!(additions <= n.count_ones());
+        }
+        assert_eq!(naf(7), vec![-1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn is_prime_matches_known_table() {
+        let primes = [
+            2,
+            3,
+            5,
+            863,
+            65537,
+            2147483647,
+            4294967291,
+            1000000007,
+            18446744073709551557,
+        ];
+        // Carmichael numbers and strong pseudoprimes to small bases, along with ordinary composites.
+        let composites = [
+            0,
+            1,
+            4,
+            15,
+            561,
+            1105,
+            1729,
+            41041,
+            825265,
+            2047,
+            3215031751,
+            3825123056546413051,
+            4294967297,
+            18446744073709551615,
+        ];
+        for &n in primes.iter() {
+            assert!(is_prime(n), "{} is prime", n);
+        }
+        for &n in composites.iter() {
+            assert!(!is_prime(n), "{} is composite", n);
+        }
+        let sieve: Vec<u64> = (0..2000u64)
+            .filter(|&n| n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+            .collect();
+        assert_eq!(
+            (0..2000).filter(|&n| is_prime(n)).collect::<Vec<_>>(),
+            sieve
+        );
+    }
+
+    #[test]
+    fn random_primes_are_3_mod_4_of_given_size() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for bits in 2..=63 {
+            let p = random_prime_3mod4(bits, &mut rng);
+            assert!(is_prime(p));
+            assert_eq!(p % 4, 3);
+            assert_eq!(64 - p.leading_zeros(), bits);
+        }
+    }
+
+    #[test]
+    fn gcd_and_lcm_of_known_values() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(18, 12), 6);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(7, 1), 7);
+        assert_eq!(lcm(0, 5), 0);
+        assert_eq!(lcm(5, 0), 0);
+        assert_eq!(lcm(1 << 32, 1 << 31), 1 << 32);
+        for a in 1..50 {
+            for b in 1..50 {
+                assert_eq!(gcd(a, b) * lcm(a, b), a * b);
+            }
+        }
+    }
+
+    #[test]
+    fn reduced_exponents_stay_positive() {
+        assert_eq!(reduce_exponent(0, 10), 0);
+        assert_eq!(reduce_exponent(3, 10), 3);
+        assert_eq!(reduce_exponent(10, 10), 10);
+        assert_eq!(reduce_exponent(11, 10), 1);
+        assert_eq!(reduce_exponent(20, 10), 10);
+        assert_eq!(reduce_exponent(u64::MAX, 1), 1);
+    }
+}